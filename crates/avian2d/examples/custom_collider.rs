@@ -103,6 +103,8 @@ impl AnyCollider for CircleCollider {
 
             vec![ContactManifold {
                 index: 0,
+                subshape_index1: None,
+                subshape_index2: None,
                 normal1,
                 normal2,
                 contacts: vec![ContactData {
@@ -116,7 +118,14 @@ impl AnyCollider for CircleCollider {
                     // Impulses are computed by the constraint solver
                     normal_impulse: 0.0,
                     tangent_impulse: 0.0,
+                    friction: None,
+                    restitution: None,
+                    tangent_velocity: None,
                 }],
+                // Material properties are computed by the narrow phase
+                friction: Friction::ZERO,
+                restitution: Restitution::ZERO,
+                tangent_velocity: Vector::ZERO,
             }]
         } else {
             vec![]
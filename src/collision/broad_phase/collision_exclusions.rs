@@ -0,0 +1,194 @@
+//! [`CollisionExclusions`] for preventing specific pairs of entities from colliding.
+
+use std::time::Duration;
+
+use crate::prelude::*;
+use bevy::{
+    ecs::entity::{Entities, EntityMapper, MapEntities},
+    prelude::*,
+    utils::HashMap,
+};
+
+use super::ordered_pair;
+
+/// Pairs of entities that should never collide with each other, optionally only for a limited time.
+///
+/// Unlike [`CollisionLayers`], which filter collisions between whole groups of entities,
+/// exclusions apply to specific pairs. This is useful for cases like a vehicle chassis
+/// and its wheels, or a held item and the player holding it.
+///
+/// An excluded entity can be either a collider or a rigid body. Excluding a rigid body
+/// excludes all of its attached colliders.
+///
+/// The broad phase skips excluded pairs before they are added to [`BroadCollisionPairs`],
+/// so no contacts are computed for them. Pairs containing despawned entities and pairs
+/// whose exclusion has expired are removed automatically in [`BroadPhaseSet::First`](super::BroadPhaseSet::First).
+///
+/// ## Example
+///
+/// ```
+#[cfg_attr(feature = "2d", doc = "use avian2d::prelude::*;")]
+#[cfg_attr(feature = "3d", doc = "use avian3d::prelude::*;")]
+/// use bevy::prelude::*;
+/// use std::time::Duration;
+///
+/// #[derive(Component)]
+/// struct Player;
+///
+/// fn throw_item(
+///     mut commands: Commands,
+///     player: Query<Entity, With<Player>>,
+///     mut exclusions: ResMut<CollisionExclusions>,
+/// ) {
+///     let Ok(player) = player.get_single() else {
+///         return;
+///     };
+///
+///     let item = commands
+///         .spawn((RigidBody::Dynamic, Collider::capsule(0.1, 0.5)))
+///         .id();
+///
+///     // Let the item leave the player before they can collide.
+///     exclusions.insert_for(player, item, Duration::from_secs_f32(0.5));
+/// }
+/// ```
+#[derive(Resource, Clone, Debug, Default, PartialEq)]
+pub struct CollisionExclusions {
+    /// The excluded pairs, with the smaller [`Entity`] first,
+    /// and the time remaining until the exclusion expires.
+    pairs: HashMap<(Entity, Entity), Option<Duration>>,
+}
+
+impl CollisionExclusions {
+    /// Prevents `entity1` and `entity2` from colliding until the exclusion is removed.
+    pub fn insert(&mut self, entity1: Entity, entity2: Entity) {
+        self.pairs.insert(ordered_pair(entity1, entity2), None);
+    }
+
+    /// Prevents `entity1` and `entity2` from colliding for the given `duration` of physics time.
+    pub fn insert_for(&mut self, entity1: Entity, entity2: Entity, duration: Duration) {
+        self.pairs
+            .insert(ordered_pair(entity1, entity2), Some(duration));
+    }
+
+    /// Removes the exclusion between `entity1` and `entity2`, allowing them to collide again.
+    ///
+    /// Returns `true` if the pair was excluded.
+    pub fn remove(&mut self, entity1: Entity, entity2: Entity) -> bool {
+        self.pairs.remove(&ordered_pair(entity1, entity2)).is_some()
+    }
+
+    /// Removes all exclusions involving the given `entity`.
+    pub fn remove_entity(&mut self, entity: Entity) {
+        self.pairs
+            .retain(|(entity1, entity2), _| *entity1 != entity && *entity2 != entity);
+    }
+
+    /// Returns `true` if `entity1` and `entity2` are excluded from colliding with each other.
+    pub fn contains(&self, entity1: Entity, entity2: Entity) -> bool {
+        self.pairs.contains_key(&ordered_pair(entity1, entity2))
+    }
+
+    /// Returns the time remaining until the exclusion between `entity1` and `entity2` expires.
+    ///
+    /// Returns `None` if the pair is not excluded or if the exclusion doesn't expire.
+    pub fn remaining(&self, entity1: Entity, entity2: Entity) -> Option<Duration> {
+        self.pairs
+            .get(&ordered_pair(entity1, entity2))
+            .copied()
+            .flatten()
+    }
+
+    /// Returns an iterator over the excluded pairs.
+    pub fn iter(&self) -> impl Iterator<Item = &(Entity, Entity)> {
+        self.pairs.keys()
+    }
+
+    /// Returns the number of excluded pairs.
+    pub fn len(&self) -> usize {
+        self.pairs.len()
+    }
+
+    /// Returns `true` if no pairs are excluded.
+    pub fn is_empty(&self) -> bool {
+        self.pairs.is_empty()
+    }
+
+    /// Removes all exclusions.
+    pub fn clear(&mut self) {
+        self.pairs.clear();
+    }
+
+    /// Returns `true` if the colliders `collider1` and `collider2`, attached to the rigid bodies
+    /// `parent1` and `parent2`, are excluded from colliding either directly or through their bodies.
+    pub(crate) fn excludes(
+        &self,
+        collider1: Entity,
+        parent1: ColliderParent,
+        collider2: Entity,
+        parent2: ColliderParent,
+    ) -> bool {
+        if self.pairs.is_empty() {
+            return false;
+        }
+
+        let (parent1, parent2) = (parent1.get(), parent2.get());
+
+        self.contains(collider1, collider2)
+            || self.contains(parent1, parent2)
+            || self.contains(collider1, parent2)
+            || self.contains(parent1, collider2)
+    }
+}
+
+impl MapEntities for CollisionExclusions {
+    fn map_entities<M: EntityMapper>(&mut self, entity_mapper: &mut M) {
+        self.pairs = self
+            .pairs
+            .drain()
+            .map(|((entity1, entity2), remaining)| {
+                let entity1 = entity_mapper.map_entity(entity1);
+                let entity2 = entity_mapper.map_entity(entity2);
+                (ordered_pair(entity1, entity2), remaining)
+            })
+            .collect();
+    }
+}
+
+/// Advances the expiry timers of the [`CollisionExclusions`], and removes pairs
+/// that have expired or contain despawned entities.
+pub(super) fn update_collision_exclusions(
+    mut exclusions: ResMut<CollisionExclusions>,
+    entities: &Entities,
+    time: Res<Time>,
+) {
+    if exclusions.is_empty() {
+        return;
+    }
+
+    let delta = time.delta();
+    let count = exclusions.len();
+
+    // Advancing the timers is not a change to the excluded pairs,
+    // so only trigger change detection if pairs were removed.
+    exclusions
+        .bypass_change_detection()
+        .pairs
+        .retain(|(entity1, entity2), remaining| {
+            if !entities.contains(*entity1) || !entities.contains(*entity2) {
+                return false;
+            }
+
+            match remaining {
+                Some(remaining) => {
+                    *remaining = remaining.saturating_sub(delta);
+                    !remaining.is_zero()
+                }
+                None => true,
+            }
+        });
+
+    if exclusions.len() != count {
+        exclusions.set_changed();
+    }
+}
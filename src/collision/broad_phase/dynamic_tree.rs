@@ -0,0 +1,812 @@
+//! A broad phase that uses an incrementally updated dynamic AABB tree.
+//!
+//! See [`DynamicTreeBroadPhasePlugin`].
+
+use super::{
+    configure_broad_phase_sets, init_collision_exclusions, init_pair_tracking, AabbIntersections,
+    BroadPhaseSet, IsBodyInactive, StoreAabbIntersections,
+};
+use crate::prelude::*;
+use bevy::{
+    ecs::entity::{EntityHashMap, EntityMapper, MapEntities},
+    prelude::*,
+};
+
+/// Collects pairs of potentially colliding entities into [`BroadCollisionPairs`] using
+/// a [dynamic AABB tree](DynamicAabbTree), a bounding volume hierarchy that is refit
+/// and rebalanced incrementally as colliders move.
+///
+/// Unlike the default sweep and prune of the [`BroadPhasePlugin`], the tree does not
+/// depend on a single sorting axis, so it scales well for worlds with tall stacks or long corridors
+/// where many colliders overlap along the x axis.
+///
+/// The plugin produces the same [`BroadCollisionPairs`] and [`AabbIntersections`](super::AabbIntersections)
+/// as the [`BroadPhasePlugin`], so it can be used as a drop-in replacement:
+///
+/// ```no_run
+#[cfg_attr(feature = "2d", doc = "use avian2d::prelude::*;")]
+#[cfg_attr(feature = "3d", doc = "use avian3d::prelude::*;")]
+/// use bevy::prelude::*;
+///
+/// fn main() {
+///     App::new()
+///         .add_plugins((
+///             DefaultPlugins,
+///             PhysicsPlugins::default()
+///                 .build()
+///                 .disable::<BroadPhasePlugin>()
+///                 .add(DynamicTreeBroadPhasePlugin),
+///         ))
+///         .run();
+/// }
+/// ```
+///
+/// The tree can be configured using the [`DynamicTreeBroadPhaseConfig`] resource.
+///
+/// The broad phase systems run in [`PhysicsStepSet::BroadPhase`].
+pub struct DynamicTreeBroadPhasePlugin;
+
+impl Plugin for DynamicTreeBroadPhasePlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<BroadCollisionPairs>()
+            .init_resource::<DynamicTreeBroadPhaseConfig>()
+            .init_resource::<AabbTree>()
+            .register_type::<DynamicTreeBroadPhaseConfig>();
+
+        configure_broad_phase_sets(app);
+        init_collision_exclusions(app);
+        init_pair_tracking(app);
+
+        let physics_schedule = app
+            .get_schedule_mut(PhysicsSchedule)
+            .expect("add PhysicsSchedule first");
+
+        physics_schedule.add_systems(update_aabb_tree.in_set(BroadPhaseSet::UpdateStructures));
+
+        physics_schedule
+            .add_systems(collect_collision_pairs.in_set(BroadPhaseSet::CollectCollisions));
+    }
+}
+
+/// A resource for configuring the [`DynamicTreeBroadPhasePlugin`].
+#[derive(Resource, Reflect, Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serialize", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serialize", reflect(Serialize, Deserialize))]
+#[reflect(Debug, Resource, PartialEq)]
+pub struct DynamicTreeBroadPhaseConfig {
+    /// The margin by which the AABBs stored in the tree are enlarged.
+    ///
+    /// A collider is only reinserted into the tree once its [`ColliderAabb`] leaves
+    /// this enlarged AABB. Larger margins reduce the number of reinsertions for moving bodies,
+    /// but produce more pairs that need to be checked for overlap.
+    ///
+    /// This is implicitly scaled by the [`PhysicsLengthUnit`].
+    ///
+    /// Default: `0.1`
+    pub aabb_margin: Scalar,
+}
+
+impl Default for DynamicTreeBroadPhaseConfig {
+    fn default() -> Self {
+        Self { aabb_margin: 0.1 }
+    }
+}
+
+/// The index used for nodes that don't exist.
+const NULL_NODE: u32 = u32::MAX;
+
+#[derive(Clone, Debug)]
+struct TreeNode<T> {
+    /// The enlarged AABB of the node. For leaves, this is the enlarged AABB of the proxy.
+    aabb: ColliderAabb,
+    /// The parent node, or the next free node if the node is in the free list.
+    parent: u32,
+    child1: u32,
+    child2: u32,
+    /// The height of the subtree. Leaves have a height of `0`, and free nodes have a height of `-1`.
+    height: i32,
+    /// The data stored in a leaf. `None` for internal and free nodes.
+    data: Option<T>,
+}
+
+impl<T> TreeNode<T> {
+    fn is_leaf(&self) -> bool {
+        self.child1 == NULL_NODE
+    }
+}
+
+/// A dynamic bounding volume hierarchy of [`ColliderAabb`]s.
+///
+/// Each leaf of the binary tree is a *proxy* that stores an enlarged AABB and some user data.
+/// Proxies are identified by the `u32` returned by [`insert`](Self::insert), which stays valid
+/// until the proxy is [removed](Self::remove).
+///
+/// When a proxy moves, it is only reinserted if its new AABB leaves the enlarged AABB,
+/// which makes updates cheap for colliders that move slowly or not at all. The tree is kept balanced
+/// using tree rotations, and insertions use a surface area heuristic to find a good sibling for new leaves.
+#[derive(Clone, Debug)]
+pub struct DynamicAabbTree<T> {
+    nodes: Vec<TreeNode<T>>,
+    root: u32,
+    free_list: u32,
+    proxy_count: usize,
+}
+
+impl<T> Default for DynamicAabbTree<T> {
+    fn default() -> Self {
+        Self {
+            nodes: Vec::new(),
+            root: NULL_NODE,
+            free_list: NULL_NODE,
+            proxy_count: 0,
+        }
+    }
+}
+
+impl<T> DynamicAabbTree<T> {
+    /// Creates an empty [`DynamicAabbTree`].
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Returns the number of proxies in the tree.
+    pub fn len(&self) -> usize {
+        self.proxy_count
+    }
+
+    /// Returns `true` if the tree contains no proxies.
+    pub fn is_empty(&self) -> bool {
+        self.proxy_count == 0
+    }
+
+    /// Returns the height of the tree. An empty tree has a height of `0`.
+    pub fn height(&self) -> u32 {
+        if self.root == NULL_NODE {
+            0
+        } else {
+            self.nodes[self.root as usize].height as u32
+        }
+    }
+
+    /// Removes all proxies from the tree.
+    pub fn clear(&mut self) {
+        *self = Self::default();
+    }
+
+    /// Returns a reference to the data of the given proxy, or `None` if it does not exist.
+    pub fn get(&self, proxy: u32) -> Option<&T> {
+        self.nodes
+            .get(proxy as usize)
+            .and_then(|node| node.data.as_ref())
+    }
+
+    /// Returns a mutable reference to the data of the given proxy, or `None` if it does not exist.
+    pub fn get_mut(&mut self, proxy: u32) -> Option<&mut T> {
+        self.nodes
+            .get_mut(proxy as usize)
+            .and_then(|node| node.data.as_mut())
+    }
+
+    /// Returns the enlarged AABB stored for the given proxy, or `None` if it does not exist.
+    pub fn fat_aabb(&self, proxy: u32) -> Option<ColliderAabb> {
+        self.nodes
+            .get(proxy as usize)
+            .filter(|node| node.data.is_some())
+            .map(|node| node.aabb)
+    }
+
+    /// Returns an iterator over the proxies in the tree and their data.
+    pub fn iter(&self) -> impl Iterator<Item = (u32, &T)> {
+        self.nodes
+            .iter()
+            .enumerate()
+            .filter_map(|(i, node)| node.data.as_ref().map(|data| (i as u32, data)))
+    }
+
+    /// Inserts a new proxy with the given AABB and data, returning the ID of the proxy.
+    ///
+    /// The AABB is stored as is. To allow the proxy to move without being reinserted,
+    /// it should typically be enlarged by some margin.
+    pub fn insert(&mut self, aabb: ColliderAabb, data: T) -> u32 {
+        let proxy = self.allocate_node();
+        let node = &mut self.nodes[proxy as usize];
+        node.aabb = aabb;
+        node.height = 0;
+        node.data = Some(data);
+
+        self.insert_leaf(proxy);
+        self.proxy_count += 1;
+
+        proxy
+    }
+
+    /// Removes the given proxy from the tree, returning its data if it existed.
+    pub fn remove(&mut self, proxy: u32) -> Option<T> {
+        let data = self.nodes.get_mut(proxy as usize)?.data.take()?;
+
+        self.remove_leaf(proxy);
+        self.free_node(proxy);
+        self.proxy_count -= 1;
+
+        Some(data)
+    }
+
+    /// Updates the AABB of the given proxy.
+    ///
+    /// If the new `aabb` is still contained in the enlarged AABB of the proxy,
+    /// and the enlarged AABB is not excessively large, nothing is done and `false` is returned.
+    /// Otherwise, the proxy is reinserted with `aabb` enlarged by `margin`, and `true` is returned.
+    pub fn update(&mut self, proxy: u32, aabb: ColliderAabb, margin: Scalar) -> bool {
+        let Some(node) = self
+            .nodes
+            .get(proxy as usize)
+            .filter(|node| node.data.is_some())
+        else {
+            return false;
+        };
+
+        let margin = Vector::splat(margin);
+        let fat_aabb = aabb.grow(margin);
+        let huge_aabb = fat_aabb.grow(4.0 * margin);
+
+        if aabb_contains(&node.aabb, &aabb) && aabb_contains(&huge_aabb, &node.aabb) {
+            return false;
+        }
+
+        self.remove_leaf(proxy);
+        self.nodes[proxy as usize].aabb = fat_aabb;
+        self.insert_leaf(proxy);
+
+        true
+    }
+
+    /// Calls the given `callback` for each proxy whose enlarged AABB intersects `aabb`.
+    ///
+    /// The traversal is stopped early if the callback returns `false`.
+    pub fn query(&self, aabb: &ColliderAabb, mut callback: impl FnMut(u32, &T) -> bool) {
+        if self.root == NULL_NODE {
+            return;
+        }
+
+        let mut stack = vec![self.root];
+
+        while let Some(index) = stack.pop() {
+            let node = &self.nodes[index as usize];
+
+            if !node.aabb.intersects(aabb) {
+                continue;
+            }
+
+            if let Some(data) = &node.data {
+                if !callback(index, data) {
+                    return;
+                }
+            } else {
+                // Push the second child first so that the first child is visited first.
+                stack.push(node.child2);
+                stack.push(node.child1);
+            }
+        }
+    }
+
+    fn allocate_node(&mut self) -> u32 {
+        if self.free_list == NULL_NODE {
+            self.nodes.push(TreeNode {
+                aabb: ColliderAabb::default(),
+                parent: NULL_NODE,
+                child1: NULL_NODE,
+                child2: NULL_NODE,
+                height: -1,
+                data: None,
+            });
+            return self.nodes.len() as u32 - 1;
+        }
+
+        let index = self.free_list;
+        let node = &mut self.nodes[index as usize];
+        self.free_list = node.parent;
+        node.parent = NULL_NODE;
+        node.child1 = NULL_NODE;
+        node.child2 = NULL_NODE;
+        node.height = 0;
+        index
+    }
+
+    fn free_node(&mut self, index: u32) {
+        let node = &mut self.nodes[index as usize];
+        node.parent = self.free_list;
+        node.child1 = NULL_NODE;
+        node.child2 = NULL_NODE;
+        node.height = -1;
+        node.data = None;
+        self.free_list = index;
+    }
+
+    fn insert_leaf(&mut self, leaf: u32) {
+        if self.root == NULL_NODE {
+            self.root = leaf;
+            self.nodes[leaf as usize].parent = NULL_NODE;
+            return;
+        }
+
+        // Find the best sibling for the leaf using the surface area heuristic.
+        let leaf_aabb = self.nodes[leaf as usize].aabb;
+        let mut index = self.root;
+
+        while !self.nodes[index as usize].is_leaf() {
+            let node = &self.nodes[index as usize];
+            let child1 = node.child1;
+            let child2 = node.child2;
+
+            let area = surface_area(&node.aabb);
+            let combined_area = surface_area(&node.aabb.merged(leaf_aabb));
+
+            // The cost of creating a new parent for this node and the new leaf.
+            let cost = 2.0 * combined_area;
+
+            // The minimum cost of pushing the leaf further down the tree.
+            let inheritance_cost = 2.0 * (combined_area - area);
+
+            let cost1 = self.descend_cost(child1, &leaf_aabb) + inheritance_cost;
+            let cost2 = self.descend_cost(child2, &leaf_aabb) + inheritance_cost;
+
+            if cost < cost1 && cost < cost2 {
+                break;
+            }
+
+            index = if cost1 < cost2 { child1 } else { child2 };
+        }
+
+        let sibling = index;
+
+        // Create a new parent for the leaf and its sibling.
+        let old_parent = self.nodes[sibling as usize].parent;
+        let new_parent = self.allocate_node();
+        {
+            let sibling_node = &self.nodes[sibling as usize];
+            let aabb = sibling_node.aabb.merged(leaf_aabb);
+            let height = sibling_node.height + 1;
+            let node = &mut self.nodes[new_parent as usize];
+            node.parent = old_parent;
+            node.aabb = aabb;
+            node.height = height;
+            node.child1 = sibling;
+            node.child2 = leaf;
+        }
+
+        if old_parent != NULL_NODE {
+            self.replace_child(old_parent, sibling, new_parent);
+        } else {
+            self.root = new_parent;
+        }
+
+        self.nodes[sibling as usize].parent = new_parent;
+        self.nodes[leaf as usize].parent = new_parent;
+
+        // Walk back up the tree, fixing heights and AABBs.
+        self.refit_ancestors(new_parent);
+    }
+
+    fn remove_leaf(&mut self, leaf: u32) {
+        if leaf == self.root {
+            self.root = NULL_NODE;
+            return;
+        }
+
+        let parent = self.nodes[leaf as usize].parent;
+        let grandparent = self.nodes[parent as usize].parent;
+        let sibling = if self.nodes[parent as usize].child1 == leaf {
+            self.nodes[parent as usize].child2
+        } else {
+            self.nodes[parent as usize].child1
+        };
+
+        if grandparent != NULL_NODE {
+            // Destroy the parent and connect the sibling to the grandparent.
+            self.replace_child(grandparent, parent, sibling);
+            self.nodes[sibling as usize].parent = grandparent;
+            self.free_node(parent);
+
+            self.refit_ancestors(grandparent);
+        } else {
+            self.root = sibling;
+            self.nodes[sibling as usize].parent = NULL_NODE;
+            self.free_node(parent);
+        }
+    }
+
+    /// Balances and refits the given node and all of its ancestors.
+    fn refit_ancestors(&mut self, mut index: u32) {
+        while index != NULL_NODE {
+            index = self.balance(index);
+
+            let node = &self.nodes[index as usize];
+            let (child1, child2) = (
+                &self.nodes[node.child1 as usize],
+                &self.nodes[node.child2 as usize],
+            );
+            let height = 1 + child1.height.max(child2.height);
+            let aabb = child1.aabb.merged(child2.aabb);
+
+            let node = &mut self.nodes[index as usize];
+            node.height = height;
+            node.aabb = aabb;
+
+            index = node.parent;
+        }
+    }
+
+    /// Returns the cost of descending into the given child when inserting a leaf with the given AABB.
+    fn descend_cost(&self, child: u32, leaf_aabb: &ColliderAabb) -> Scalar {
+        let child = &self.nodes[child as usize];
+        let combined_area = surface_area(&child.aabb.merged(*leaf_aabb));
+        if child.is_leaf() {
+            combined_area
+        } else {
+            combined_area - surface_area(&child.aabb)
+        }
+    }
+
+    fn replace_child(&mut self, parent: u32, old_child: u32, new_child: u32) {
+        let parent = &mut self.nodes[parent as usize];
+        if parent.child1 == old_child {
+            parent.child1 = new_child;
+        } else {
+            parent.child2 = new_child;
+        }
+    }
+
+    /// Performs a left or right rotation if the subtree rooted at `a` is imbalanced.
+    /// Returns the new root of the subtree.
+    fn balance(&mut self, a: u32) -> u32 {
+        let node_a = &self.nodes[a as usize];
+        if node_a.is_leaf() || node_a.height < 2 {
+            return a;
+        }
+
+        let b = node_a.child1;
+        let c = node_a.child2;
+        let balance = self.nodes[c as usize].height - self.nodes[b as usize].height;
+
+        if balance > 1 {
+            // Rotate C up.
+            self.rotate_up(a, c, b, false)
+        } else if balance < -1 {
+            // Rotate B up.
+            self.rotate_up(a, b, c, true)
+        } else {
+            a
+        }
+    }
+
+    /// Rotates the child `up` of `a` to take the place of `a`, with `a` becoming a child of `up`.
+    /// `other` is the other child of `a`, and `up_is_child1` tells which slot `up` occupies in `a`.
+    fn rotate_up(&mut self, a: u32, up: u32, other: u32, up_is_child1: bool) -> u32 {
+        let f = self.nodes[up as usize].child1;
+        let g = self.nodes[up as usize].child2;
+        let a_parent = self.nodes[a as usize].parent;
+
+        // Swap A and `up`.
+        self.nodes[up as usize].child1 = a;
+        self.nodes[up as usize].parent = a_parent;
+        self.nodes[a as usize].parent = up;
+
+        // A's old parent should point to `up`.
+        if a_parent != NULL_NODE {
+            self.replace_child(a_parent, a, up);
+        } else {
+            self.root = up;
+        }
+
+        // Keep the taller grandchild under `up` and move the shorter one to A.
+        let (keep, moved) = if self.nodes[f as usize].height > self.nodes[g as usize].height {
+            (f, g)
+        } else {
+            (g, f)
+        };
+
+        self.nodes[up as usize].child2 = keep;
+        if up_is_child1 {
+            self.nodes[a as usize].child1 = moved;
+        } else {
+            self.nodes[a as usize].child2 = moved;
+        }
+        self.nodes[moved as usize].parent = a;
+
+        let other_node = &self.nodes[other as usize];
+        let moved_node = &self.nodes[moved as usize];
+        let a_aabb = other_node.aabb.merged(moved_node.aabb);
+        let a_height = 1 + other_node.height.max(moved_node.height);
+
+        let keep_node = &self.nodes[keep as usize];
+        let up_aabb = a_aabb.merged(keep_node.aabb);
+        let up_height = 1 + a_height.max(keep_node.height);
+
+        let node_a = &mut self.nodes[a as usize];
+        node_a.aabb = a_aabb;
+        node_a.height = a_height;
+
+        let node_up = &mut self.nodes[up as usize];
+        node_up.aabb = up_aabb;
+        node_up.height = up_height;
+
+        up
+    }
+}
+
+/// Returns `true` if `outer` fully contains `inner`.
+fn aabb_contains(outer: &ColliderAabb, inner: &ColliderAabb) -> bool {
+    outer.min.cmple(inner.min).all() && outer.max.cmpge(inner.max).all()
+}
+
+/// The cost metric used by the surface area heuristic: the perimeter in 2D and the surface area in 3D.
+fn surface_area(aabb: &ColliderAabb) -> Scalar {
+    let extents = aabb.size();
+    #[cfg(feature = "2d")]
+    {
+        2.0 * (extents.x + extents.y)
+    }
+    #[cfg(feature = "3d")]
+    {
+        2.0 * (extents.x * extents.y + extents.y * extents.z + extents.z * extents.x)
+    }
+}
+
+/// The data stored for each collider in the [`AabbTree`].
+#[derive(Clone, Copy, Debug)]
+struct TreeProxy {
+    entity: Entity,
+    parent: ColliderParent,
+    aabb: ColliderAabb,
+    layers: CollisionLayers,
+    store_intersections: StoreAabbIntersections,
+    is_inactive: IsBodyInactive,
+}
+
+/// A [`DynamicAabbTree`] containing the [`ColliderAabb`]s of all colliders,
+/// along with a mapping from collider entities to their proxies.
+#[derive(Resource, Default)]
+struct AabbTree {
+    tree: DynamicAabbTree<TreeProxy>,
+    proxies: EntityHashMap<u32>,
+}
+
+impl MapEntities for AabbTree {
+    fn map_entities<M: EntityMapper>(&mut self, entity_mapper: &mut M) {
+        let proxies = std::mem::take(&mut self.proxies);
+        for (entity, proxy) in proxies {
+            let entity = entity_mapper.map_entity(entity);
+            if let Some(data) = self.tree.get_mut(proxy) {
+                data.entity = entity;
+            }
+            self.proxies.insert(entity, proxy);
+        }
+    }
+}
+
+/// Inserts, updates and removes the proxies of the [`AabbTree`]
+/// to keep them in sync with the [`ColliderAabb`]s.
+#[allow(clippy::type_complexity)]
+fn update_aabb_tree(
+    aabbs: Query<(
+        Entity,
+        Ref<ColliderAabb>,
+        Option<&ColliderParent>,
+        Option<&CollisionLayers>,
+        Has<AabbIntersections>,
+        Has<Sleeping>,
+    )>,
+    rbs: Query<&RigidBody>,
+    mut removed_aabbs: RemovedComponents<ColliderAabb>,
+    mut tree: ResMut<AabbTree>,
+    config: Res<DynamicTreeBroadPhaseConfig>,
+    length_unit: Res<PhysicsLengthUnit>,
+) {
+    let AabbTree { tree, proxies } = &mut *tree;
+    let margin = length_unit.0 * config.aabb_margin;
+
+    // Remove proxies of despawned colliders and colliders whose AABB was removed.
+    for entity in removed_aabbs.read() {
+        if aabbs.contains(entity) {
+            continue;
+        }
+        if let Some(proxy) = proxies.remove(&entity) {
+            tree.remove(proxy);
+        }
+    }
+
+    for (entity, aabb, parent, layers, store_intersections, is_sleeping) in &aabbs {
+        // Non-finite AABBs can not be stored in the tree.
+        if !aabb.min.is_finite() || !aabb.max.is_finite() {
+            if let Some(proxy) = proxies.remove(&entity) {
+                tree.remove(proxy);
+            }
+            continue;
+        }
+
+        let parent = parent.map_or(ColliderParent(entity), |p| *p);
+        let is_static = rbs.get(parent.get()).is_ok_and(RigidBody::is_static);
+
+        let proxy_data = TreeProxy {
+            entity,
+            parent,
+            aabb: *aabb,
+            layers: layers.map_or(CollisionLayers::default(), |layers| *layers),
+            store_intersections,
+            is_inactive: is_static || is_sleeping,
+        };
+
+        if let Some(&proxy) = proxies.get(&entity) {
+            if aabb.is_changed() {
+                tree.update(proxy, *aabb, margin);
+            }
+            if let Some(data) = tree.get_mut(proxy) {
+                *data = proxy_data;
+            }
+        } else {
+            let proxy = tree.insert(aabb.grow(Vector::splat(margin)), proxy_data);
+            proxies.insert(entity, proxy);
+        }
+    }
+}
+
+/// Collects bodies that are potentially colliding by querying the [`AabbTree`]
+/// with the AABB of each active collider.
+fn collect_collision_pairs(
+    tree: Res<AabbTree>,
+    exclusions: Res<CollisionExclusions>,
+    mut broad_collision_pairs: ResMut<BroadCollisionPairs>,
+    mut aabb_intersection_query: Query<&mut AabbIntersections>,
+) {
+    for mut intersections in &mut aabb_intersection_query {
+        intersections.clear();
+    }
+
+    broad_collision_pairs.clear();
+
+    for (proxy1, data1) in tree.tree.iter() {
+        // Pairs where both bodies are inactive are skipped,
+        // so only active colliders need to query the tree.
+        if data1.is_inactive {
+            continue;
+        }
+
+        tree.tree.query(&data1.aabb, |proxy2, data2| {
+            // Each pair of active colliders is found twice, so only handle it once.
+            if proxy1 == proxy2 || (!data2.is_inactive && proxy2 < proxy1) {
+                return true;
+            }
+
+            // No collisions between colliders with incompatible layers or colliders with the same parent
+            if !data1.layers.interacts_with(data2.layers) || data1.parent == data2.parent {
+                return true;
+            }
+
+            // The enlarged AABBs intersect, but the actual AABBs might not.
+            if !data1.aabb.intersects(&data2.aabb) {
+                return true;
+            }
+
+            let (ent1, ent2) = (data1.entity, data2.entity);
+
+            if exclusions.excludes(ent1, data1.parent, ent2, data2.parent) {
+                return true;
+            }
+
+            if ent1 < ent2 {
+                broad_collision_pairs.push((ent1, ent2));
+            } else {
+                broad_collision_pairs.push((ent2, ent1));
+            }
+
+            if data1.store_intersections {
+                if let Ok(mut intersections) = aabb_intersection_query.get_mut(ent1) {
+                    intersections.push(ent2);
+                }
+            }
+            if data2.store_intersections {
+                if let Ok(mut intersections) = aabb_intersection_query.get_mut(ent2) {
+                    intersections.push(ent1);
+                }
+            }
+
+            true
+        });
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn aabb(min: [Scalar; 3], max: [Scalar; 3]) -> ColliderAabb {
+        #[cfg(feature = "2d")]
+        {
+            ColliderAabb::from_min_max(Vector::new(min[0], min[1]), Vector::new(max[0], max[1]))
+        }
+        #[cfg(feature = "3d")]
+        {
+            ColliderAabb::from_min_max(Vector::from(min), Vector::from(max))
+        }
+    }
+
+    fn brute_force_overlaps(aabbs: &[ColliderAabb], query: &ColliderAabb) -> Vec<usize> {
+        aabbs
+            .iter()
+            .enumerate()
+            .filter(|(_, aabb)| aabb.intersects(query))
+            .map(|(i, _)| i)
+            .collect()
+    }
+
+    /// Checks that the parent pointers, heights and AABBs of the tree are consistent.
+    fn validate<T>(tree: &DynamicAabbTree<T>, index: u32, parent: u32) -> i32 {
+        let node = &tree.nodes[index as usize];
+        assert_eq!(node.parent, parent);
+
+        if node.is_leaf() {
+            assert!(node.data.is_some());
+            assert_eq!(node.height, 0);
+            return 0;
+        }
+
+        let height1 = validate(tree, node.child1, index);
+        let height2 = validate(tree, node.child2, index);
+        assert_eq!(node.height, 1 + height1.max(height2));
+
+        let merged = tree.nodes[node.child1 as usize]
+            .aabb
+            .merged(tree.nodes[node.child2 as usize].aabb);
+        assert!(aabb_contains(&node.aabb, &merged));
+
+        node.height
+    }
+
+    #[test]
+    fn dynamic_aabb_tree_matches_brute_force() {
+        let mut tree = DynamicAabbTree::new();
+        let mut aabbs = Vec::new();
+        let mut proxies = Vec::new();
+
+        // A long row of colliders along the x axis, stacked on top of each other.
+        for i in 0..200 {
+            let x = (i % 50) as Scalar * 0.9;
+            let y = (i / 50) as Scalar * 0.9;
+            let aabb = aabb([x, y, 0.0], [x + 1.0, y + 1.0, 1.0]);
+            aabbs.push(aabb);
+            proxies.push(tree.insert(aabb, i));
+        }
+
+        validate(&tree, tree.root, NULL_NODE);
+        assert_eq!(tree.len(), 200);
+
+        // Move every other collider upwards.
+        for i in (0..200).step_by(2) {
+            let offset = Vector::Y * 10.0;
+            aabbs[i] = ColliderAabb::from_min_max(aabbs[i].min + offset, aabbs[i].max + offset);
+            assert!(tree.update(proxies[i], aabbs[i], 0.0));
+        }
+
+        // Remove a few colliders.
+        for i in (0..200).step_by(7) {
+            assert_eq!(tree.remove(proxies[i]), Some(i));
+            aabbs[i] = ColliderAabb::default();
+        }
+
+        validate(&tree, tree.root, NULL_NODE);
+
+        for query in [
+            aabb([0.0, 0.0, 0.0], [5.0, 5.0, 5.0]),
+            aabb([10.0, 9.0, 0.0], [20.0, 12.0, 1.0]),
+            aabb([-5.0, -5.0, -5.0], [100.0, 100.0, 100.0]),
+        ] {
+            let mut found = Vec::new();
+            tree.query(&query, |_, data| {
+                found.push(*data);
+                true
+            });
+            found.sort();
+            assert_eq!(found, brute_force_overlaps(&aabbs, &query));
+        }
+    }
+}
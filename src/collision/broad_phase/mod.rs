@@ -0,0 +1,933 @@
+//! Collects pairs of potentially colliding entities into [`BroadCollisionPairs`] using
+//! [AABB](ColliderAabb) intersection checks.
+//!
+//! See [`BroadPhasePlugin`], [`DynamicTreeBroadPhasePlugin`] and [`SpatialHashBroadPhasePlugin`].
+
+mod collision_exclusions;
+mod dynamic_tree;
+mod spatial_hash;
+pub use collision_exclusions::CollisionExclusions;
+pub use dynamic_tree::{DynamicAabbTree, DynamicTreeBroadPhaseConfig, DynamicTreeBroadPhasePlugin};
+pub use spatial_hash::{SpatialHashBroadPhaseConfig, SpatialHashBroadPhasePlugin};
+
+use std::ops::Range;
+
+use crate::prelude::*;
+#[cfg(feature = "parallel")]
+use bevy::tasks::{ComputeTaskPool, ParallelSlice};
+use bevy::{
+    ecs::{
+        entity::{EntityMapper, MapEntities},
+        system::SystemParam,
+    },
+    prelude::*,
+    utils::{Entry, HashMap, HashSet},
+};
+
+/// Collects pairs of potentially colliding entities into [`BroadCollisionPairs`] using
+/// [AABB](ColliderAabb) intersection checks. This speeds up narrow phase collision detection,
+/// as the number of precise collision checks required is greatly reduced.
+///
+/// Currently, the broad phase uses the [sweep and prune](https://en.wikipedia.org/wiki/Sweep_and_prune) algorithm.
+/// For worlds where many colliders line up along the x axis, the [`DynamicTreeBroadPhasePlugin`]
+/// can be used instead. For worlds with many colliders of a similar size, consider
+/// the [`SpatialHashBroadPhasePlugin`].
+///
+/// With the `parallel` feature enabled, the pair search is split across the [compute task pool](bevy::tasks::ComputeTaskPool).
+/// The rest of the broad phase is still single-threaded: sorting the AABBs, merging the found pairs
+/// into [`BroadCollisionPairs`] while checking [`CollisionExclusions`], and updating [`AabbIntersections`].
+///
+/// Pairs of colliders are filtered using [`CollisionLayers`] and [`CollisionExclusions`].
+///
+/// The [`BroadCollisionPairs`] persist across physics steps. Only the pairs of colliders that were added,
+/// removed or changed are updated, and the changes are reported in [`BroadCollisionPairChanges`].
+///
+/// The broad phase systems run in [`PhysicsStepSet::BroadPhase`].
+pub struct BroadPhasePlugin;
+
+impl Plugin for BroadPhasePlugin {
+    fn build(&self, app: &mut App) {
+        // The pairs are tracked incrementally by the sweep itself.
+        app.init_resource::<BroadCollisionPairs>()
+            .init_resource::<BroadCollisionPairChanges>()
+            .init_resource::<AabbIntervals>();
+
+        configure_broad_phase_sets(app);
+        init_collision_exclusions(app);
+
+        let physics_schedule = app
+            .get_schedule_mut(PhysicsSchedule)
+            .expect("add PhysicsSchedule first");
+
+        physics_schedule.add_systems(
+            (update_aabb_intervals, add_new_aabb_intervals)
+                .chain()
+                .in_set(BroadPhaseSet::UpdateStructures),
+        );
+
+        physics_schedule
+            .add_systems(collect_collision_pairs.in_set(BroadPhaseSet::CollectCollisions));
+    }
+}
+
+/// System sets for systems running in [`PhysicsStepSet::BroadPhase`].
+#[derive(SystemSet, Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum BroadPhaseSet {
+    /// Runs at the start of the broad phase. Empty by default.
+    First,
+    /// Updates acceleration structures and other data needed for broad phase collision detection.
+    UpdateStructures,
+    /// Detects potential intersections between entities and adds them to the [`BroadCollisionPairs`] resource.
+    CollectCollisions,
+    /// Runs at the end of the broad phase. Empty by default.
+    Last,
+}
+
+/// Configures the [`BroadPhaseSet`]s in the [`PhysicsSchedule`].
+///
+/// Shared by the different broad phase plugins.
+fn configure_broad_phase_sets(app: &mut App) {
+    app.configure_sets(
+        PhysicsSchedule,
+        (
+            BroadPhaseSet::First,
+            BroadPhaseSet::UpdateStructures,
+            BroadPhaseSet::CollectCollisions,
+            BroadPhaseSet::Last,
+        )
+            .chain()
+            .in_set(PhysicsStepSet::BroadPhase),
+    );
+}
+
+/// Initializes [`CollisionExclusions`] and the system that removes expired exclusions.
+///
+/// Shared by the different broad phase plugins.
+fn init_collision_exclusions(app: &mut App) {
+    app.init_resource::<CollisionExclusions>();
+
+    app.get_schedule_mut(PhysicsSchedule)
+        .expect("add PhysicsSchedule first")
+        .add_systems(
+            collision_exclusions::update_collision_exclusions.in_set(BroadPhaseSet::First),
+        );
+}
+
+/// A list of entity pairs for potential collisions collected during the broad phase.
+///
+/// With the built-in broad phases, the list persists across physics steps and is kept in sync
+/// with [`BroadCollisionPairChanges`]. It also contains the pairs of colliders that are both static or sleeping,
+/// which the narrow phase skips. The list should not be modified outside of the broad phase.
+#[derive(Reflect, Resource, Debug, Default, Deref, DerefMut)]
+#[cfg_attr(feature = "serialize", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serialize", reflect(Serialize, Deserialize))]
+#[reflect(Resource)]
+pub struct BroadCollisionPairs(pub Vec<(Entity, Entity)>);
+
+/// Initializes [`BroadCollisionPairChanges`] and the system that keeps it up to date
+/// for broad phases that collect all [`BroadCollisionPairs`] from scratch on every physics step.
+///
+/// Shared by the different broad phase plugins.
+fn init_pair_tracking(app: &mut App) {
+    app.init_resource::<BroadCollisionPairChanges>();
+
+    app.get_schedule_mut(PhysicsSchedule)
+        .expect("add PhysicsSchedule first")
+        .add_systems(track_broad_collision_pairs.in_set(BroadPhaseSet::Last));
+}
+
+/// Keeps track of the entity pairs that are overlapping in the broad phase across physics steps,
+/// and reports which pairs started or stopped overlapping during the current physics step.
+///
+/// This can be used by systems that only need to react to changes in the broad phase,
+/// such as cleaning up state stored for a pair when the pair ends.
+///
+/// Pairs are stored with the smaller [`Entity`] first, in the same order as in [`BroadCollisionPairs`].
+/// Pairs of colliders that are both static or sleeping are kept until one of them becomes active again,
+/// so a pair only ends when the colliders stop overlapping while either of them is active,
+/// or when either collider is removed. [`CollisionHooks::filter_pairs`] doesn't affect the tracked pairs.
+///
+/// The [`BroadPhasePlugin`] updates the pairs incrementally: only the pairs of colliders that were added,
+/// removed, or changed their [`ColliderAabb`], [`CollisionLayers`] or activity are updated.
+/// Changes to the [`CollisionExclusions`] update all pairs. The [`DynamicTreeBroadPhasePlugin`]
+/// and [`SpatialHashBroadPhasePlugin`] collect all pairs on every physics step, so updating the changes
+/// takes time proportional to the number of pairs.
+///
+/// The resource is up to date from [`BroadPhaseSet::Last`] onwards.
+///
+/// ## Example
+///
+/// ```
+#[cfg_attr(feature = "2d", doc = "use avian2d::prelude::*;")]
+#[cfg_attr(feature = "3d", doc = "use avian3d::prelude::*;")]
+/// use bevy::prelude::*;
+///
+/// fn print_pair_changes(pair_changes: Res<BroadCollisionPairChanges>) {
+///     for (entity1, entity2) in pair_changes.started() {
+///         println!("{entity1} and {entity2} started overlapping");
+///     }
+///     for (entity1, entity2) in pair_changes.ended() {
+///         println!("{entity1} and {entity2} stopped overlapping");
+///     }
+/// }
+/// ```
+#[derive(Resource, Clone, Debug, Default)]
+pub struct BroadCollisionPairChanges {
+    /// The index of each overlapping pair in [`BroadCollisionPairs`].
+    indices: HashMap<(Entity, Entity), usize>,
+    /// The entities that each entity is overlapping with.
+    neighbors: HashMap<Entity, Vec<Entity>>,
+    started: Vec<(Entity, Entity)>,
+    ended: Vec<(Entity, Entity)>,
+}
+
+impl BroadCollisionPairChanges {
+    /// Returns the pairs that started overlapping during the current physics step.
+    pub fn started(&self) -> &[(Entity, Entity)] {
+        &self.started
+    }
+
+    /// Returns the pairs that stopped overlapping during the current physics step.
+    ///
+    /// This includes pairs where either entity was despawned or had its collider removed.
+    pub fn ended(&self) -> &[(Entity, Entity)] {
+        &self.ended
+    }
+
+    /// Returns `true` if the given entities are currently overlapping in the broad phase.
+    pub fn contains(&self, entity1: Entity, entity2: Entity) -> bool {
+        self.indices.contains_key(&ordered_pair(entity1, entity2))
+    }
+
+    /// Returns an iterator over all pairs that are currently overlapping in the broad phase.
+    pub fn iter(&self) -> impl Iterator<Item = &(Entity, Entity)> {
+        self.indices.keys()
+    }
+
+    /// Returns the number of pairs that are currently overlapping in the broad phase.
+    pub fn len(&self) -> usize {
+        self.indices.len()
+    }
+
+    /// Returns `true` if no pairs are currently overlapping in the broad phase.
+    pub fn is_empty(&self) -> bool {
+        self.indices.is_empty()
+    }
+
+    /// Returns the entities that the given `entity` is currently overlapping with.
+    fn neighbors(&self, entity: Entity) -> &[Entity] {
+        self.neighbors.get(&entity).map_or(&[], Vec::as_slice)
+    }
+
+    /// Clears the pairs that started or stopped overlapping during the previous physics step.
+    fn clear_changes(&mut self) {
+        self.started.clear();
+        self.ended.clear();
+    }
+
+    /// Adds a pair that started overlapping to the end of the `pairs`, unless it is already overlapping.
+    fn start(&mut self, pair: (Entity, Entity), pairs: &mut Vec<(Entity, Entity)>) {
+        if let Entry::Vacant(entry) = self.indices.entry(pair) {
+            entry.insert(pairs.len());
+            pairs.push(pair);
+            self.add_neighbors(pair);
+            self.started.push(pair);
+        }
+    }
+
+    /// Removes a pair that stopped overlapping from the `pairs`, if it was overlapping.
+    ///
+    /// The last pair is moved into its place, so this doesn't depend on the number of pairs.
+    fn end(&mut self, pair: (Entity, Entity), pairs: &mut Vec<(Entity, Entity)>) {
+        let Some(index) = self.indices.remove(&pair) else {
+            return;
+        };
+
+        pairs.swap_remove(index);
+        if let Some(&moved_pair) = pairs.get(index) {
+            self.indices.insert(moved_pair, index);
+        }
+
+        self.remove_neighbors(pair);
+        self.ended.push(pair);
+    }
+
+    /// Ends all pairs of the given `entity`, for example when its collider was removed.
+    fn end_all(&mut self, entity: Entity, pairs: &mut Vec<(Entity, Entity)>) {
+        for other in self.neighbors.remove(&entity).unwrap_or_default() {
+            self.end(ordered_pair(entity, other), pairs);
+        }
+    }
+
+    fn add_neighbors(&mut self, (entity1, entity2): (Entity, Entity)) {
+        self.neighbors.entry(entity1).or_default().push(entity2);
+        self.neighbors.entry(entity2).or_default().push(entity1);
+    }
+
+    fn remove_neighbors(&mut self, (entity1, entity2): (Entity, Entity)) {
+        for (entity, other) in [(entity1, entity2), (entity2, entity1)] {
+            if let Entry::Occupied(mut entry) = self.neighbors.entry(entity) {
+                let neighbors = entry.get_mut();
+                if let Some(index) = neighbors.iter().position(|&e| e == other) {
+                    neighbors.swap_remove(index);
+                }
+                if neighbors.is_empty() {
+                    entry.remove();
+                }
+            }
+        }
+    }
+}
+
+/// Returns the pair with the smaller [`Entity`] first.
+fn ordered_pair(entity1: Entity, entity2: Entity) -> (Entity, Entity) {
+    if entity1 <= entity2 {
+        (entity1, entity2)
+    } else {
+        (entity2, entity1)
+    }
+}
+
+/// Determines whether colliders are attached to static or sleeping rigid bodies.
+///
+/// The broad phase skips pairs of colliders that are both inactive, but keeps tracking them.
+#[derive(SystemParam)]
+struct ColliderActivity<'w, 's> {
+    colliders: Query<'w, 's, Option<&'static ColliderParent>, With<ColliderAabb>>,
+    bodies: Query<'w, 's, (&'static RigidBody, Has<Sleeping>)>,
+}
+
+impl ColliderActivity<'_, '_> {
+    /// Returns `true` if the collider is attached to a static or sleeping rigid body.
+    ///
+    /// Removed colliders are not inactive.
+    fn is_inactive(&self, entity: Entity) -> bool {
+        self.colliders.get(entity).is_ok_and(|parent| {
+            parent.is_some_and(|parent| {
+                self.bodies
+                    .get(parent.get())
+                    .is_ok_and(|(rb, is_sleeping)| rb.is_static() || is_sleeping)
+            })
+        })
+    }
+
+    /// Returns `true` if both colliders of the pair are inactive.
+    fn is_pair_inactive(&self, (entity1, entity2): (Entity, Entity)) -> bool {
+        self.is_inactive(entity1) && self.is_inactive(entity2)
+    }
+}
+
+/// Updates [`BroadCollisionPairChanges`] for broad phases that collect all [`BroadCollisionPairs`]
+/// from scratch on every physics step.
+///
+/// Pairs that were not reported by the broad phase are ended, unless both colliders are static or sleeping.
+/// Those pairs are added back to the [`BroadCollisionPairs`], so that they match the tracked pairs.
+fn track_broad_collision_pairs(
+    mut broad_collision_pairs: ResMut<BroadCollisionPairs>,
+    mut pair_changes: ResMut<BroadCollisionPairChanges>,
+    activity: ColliderActivity,
+) {
+    let pair_changes = &mut *pair_changes;
+    pair_changes.clear_changes();
+
+    let reported_pairs = std::mem::take(&mut broad_collision_pairs.0);
+    let pairs = &mut broad_collision_pairs.0;
+    let mut indices = HashMap::with_capacity(pair_changes.indices.len());
+
+    for (entity1, entity2) in reported_pairs {
+        let pair = ordered_pair(entity1, entity2);
+        if let Entry::Vacant(entry) = indices.entry(pair) {
+            entry.insert(pairs.len());
+            pairs.push(pair);
+
+            if !pair_changes.indices.contains_key(&pair) {
+                pair_changes.add_neighbors(pair);
+                pair_changes.started.push(pair);
+            }
+        }
+    }
+
+    // Sort for determinism, as the order of the hash map is not stable.
+    let mut unreported_pairs: Vec<_> = pair_changes
+        .indices
+        .keys()
+        .filter(|pair| !indices.contains_key(*pair))
+        .copied()
+        .collect();
+    unreported_pairs.sort_unstable();
+
+    for pair in unreported_pairs {
+        // The broad phase skips pairs where both colliders are inactive,
+        // but they are still overlapping.
+        if activity.is_pair_inactive(pair) {
+            indices.insert(pair, pairs.len());
+            pairs.push(pair);
+        } else {
+            pair_changes.remove_neighbors(pair);
+            pair_changes.ended.push(pair);
+        }
+    }
+
+    pair_changes.indices = indices;
+}
+
+/// Contains the entities whose AABBs intersect the AABB of this entity.
+/// Updated automatically during broad phase collision detection.
+///
+/// Note that this component is only added to bodies with [`SweptCcd`] by default,
+/// but can be added to any entity.
+#[derive(Component, Clone, Debug, Default, Deref, DerefMut, Reflect)]
+#[reflect(Component)]
+pub struct AabbIntersections(pub Vec<Entity>);
+
+/// True if the rigid body should store [`AabbIntersections`].
+type StoreAabbIntersections = bool;
+
+/// True if the rigid body hasn't moved.
+type IsBodyInactive = bool;
+
+/// True if the collider was added, or its AABB, layers or activity changed since the previous sweep.
+type IsIntervalChanged = bool;
+
+/// The data of a collider used for sweep and prune.
+type AabbInterval = (
+    Entity,
+    ColliderParent,
+    ColliderAabb,
+    CollisionLayers,
+    StoreAabbIntersections,
+    IsBodyInactive,
+    IsIntervalChanged,
+);
+
+/// A marker component for colliders that were removed from the broad phase
+/// because their [`ColliderAabb`] is not finite.
+#[derive(Component, Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub(crate) struct NonFiniteAabb;
+
+/// Entities with [`ColliderAabb`]s sorted along an axis by their extents.
+#[derive(Resource, Default)]
+struct AabbIntervals(Vec<AabbInterval>);
+
+impl MapEntities for AabbIntervals {
+    fn map_entities<M: EntityMapper>(&mut self, entity_mapper: &mut M) {
+        for interval in self.0.iter_mut() {
+            interval.0 = entity_mapper.map_entity(interval.0);
+        }
+    }
+}
+
+/// Updates [`AabbIntervals`] to keep them in sync with the [`ColliderAabb`]s,
+/// and ends the [`BroadCollisionPairChanges`] of removed colliders.
+///
+/// Colliders with non-finite AABBs are removed and marked with [`NonFiniteAabb`].
+#[allow(clippy::type_complexity)]
+fn update_aabb_intervals(
+    mut commands: Commands,
+    aabbs: Query<(
+        &ColliderAabb,
+        Option<&ColliderParent>,
+        Option<&CollisionLayers>,
+        Has<AabbIntersections>,
+        Has<Sleeping>,
+    )>,
+    rbs: Query<&RigidBody>,
+    mut intervals: ResMut<AabbIntervals>,
+    mut broad_collision_pairs: ResMut<BroadCollisionPairs>,
+    mut pair_changes: ResMut<BroadCollisionPairChanges>,
+) {
+    pair_changes.clear_changes();
+
+    intervals.0.retain_mut(
+        |(
+            collider_entity,
+            collider_parent,
+            aabb,
+            layers,
+            store_intersections,
+            is_inactive,
+            is_changed,
+        )| {
+            let Ok((new_aabb, new_parent, new_layers, new_store_intersections, is_sleeping)) =
+                aabbs.get(*collider_entity)
+            else {
+                pair_changes.end_all(*collider_entity, &mut broad_collision_pairs);
+                return false;
+            };
+
+            // Non-finite AABBs can't be sorted, so the collider is removed from the broad phase.
+            // The `WorldBoundsPlugin` handles such bodies as out of bounds.
+            if !new_aabb.min.is_finite() || !new_aabb.max.is_finite() {
+                warn!(
+                    "{collider_entity:?} has a non-finite AABB and was removed from the broad phase"
+                );
+                commands.entity(*collider_entity).insert(NonFiniteAabb);
+                pair_changes.end_all(*collider_entity, &mut broad_collision_pairs);
+                return false;
+            }
+
+            let is_static =
+                new_parent.is_some_and(|p| rbs.get(p.get()).is_ok_and(RigidBody::is_static));
+            let new_is_inactive = is_static || is_sleeping;
+            let new_parent = new_parent.map_or(ColliderParent(*collider_entity), |p| *p);
+            let new_layers = new_layers.map_or(CollisionLayers::default(), |layers| *layers);
+
+            // Only the pairs of changed colliders need to be updated by the sweep.
+            *is_changed = *aabb != *new_aabb
+                || *collider_parent != new_parent
+                || *layers != new_layers
+                || *is_inactive != new_is_inactive;
+
+            *aabb = *new_aabb;
+            *collider_parent = new_parent;
+            *layers = new_layers;
+            *is_inactive = new_is_inactive;
+            *store_intersections = new_store_intersections;
+
+            true
+        },
+    );
+}
+
+/// Adds new [`ColliderAabb`]s to [`AabbIntervals`].
+#[allow(clippy::type_complexity)]
+fn add_new_aabb_intervals(
+    aabbs: Query<
+        (
+            Entity,
+            Option<&ColliderParent>,
+            &ColliderAabb,
+            Option<&RigidBody>,
+            Option<&CollisionLayers>,
+            Has<AabbIntersections>,
+        ),
+        Added<ColliderAabb>,
+    >,
+    mut intervals: ResMut<AabbIntervals>,
+) {
+    let aabbs = aabbs
+        .iter()
+        .map(|(ent, parent, aabb, rb, layers, store_intersections)| {
+            (
+                ent,
+                parent.map_or(ColliderParent(ent), |p| *p),
+                *aabb,
+                layers.map_or(CollisionLayers::default(), |layers| *layers),
+                store_intersections,
+                // Default to treating collider as immovable/static for filtering unnecessary collision checks
+                rb.map_or(false, |rb| rb.is_static()),
+                true,
+            )
+        });
+    intervals.0.extend(aabbs);
+}
+
+/// Collects bodies that are potentially colliding.
+///
+/// Only the pairs of changed colliders are updated, so the other [`BroadCollisionPairs`]
+/// are kept as they are.
+#[allow(clippy::too_many_arguments)]
+fn collect_collision_pairs(
+    mut intervals: ResMut<AabbIntervals>,
+    exclusions: Res<CollisionExclusions>,
+    mut broad_collision_pairs: ResMut<BroadCollisionPairs>,
+    mut pair_changes: ResMut<BroadCollisionPairChanges>,
+    mut aabb_intersection_query: Query<&mut AabbIntersections>,
+    activity: ColliderActivity,
+) {
+    // Exclusions can be added or removed for any pair, so all pairs need to be updated.
+    if exclusions.is_changed() {
+        for interval in intervals.0.iter_mut() {
+            interval.6 = true;
+        }
+    }
+
+    let found_pairs = sweep_and_prune(&mut intervals, &exclusions);
+
+    let pair_changes = &mut *pair_changes;
+    let pairs = &mut broad_collision_pairs.0;
+
+    // End the pairs of changed colliders that were not found again.
+    // The sweep skips pairs where both colliders are inactive, but they are still overlapping.
+    let found_pair_set: HashSet<(Entity, Entity)> = found_pairs.iter().copied().collect();
+    let ended_pairs: Vec<(Entity, Entity)> = intervals
+        .0
+        .iter()
+        .filter(|interval| interval.6)
+        .flat_map(|interval| {
+            pair_changes
+                .neighbors(interval.0)
+                .iter()
+                .map(|&other| ordered_pair(interval.0, other))
+        })
+        .filter(|pair| !found_pair_set.contains(pair) && !activity.is_pair_inactive(*pair))
+        .collect();
+
+    for pair in ended_pairs {
+        pair_changes.end(pair, pairs);
+    }
+    for pair in found_pairs {
+        pair_changes.start(pair, pairs);
+    }
+
+    for (entity, _, _, _, store_intersections, _, is_changed) in intervals.0.iter_mut() {
+        if *store_intersections {
+            if let Ok(mut intersections) = aabb_intersection_query.get_mut(*entity) {
+                intersections.clear();
+                intersections.extend_from_slice(pair_changes.neighbors(*entity));
+            }
+        }
+
+        *is_changed = false;
+    }
+}
+
+/// Sorts the entities by their minimum extents along an axis and returns the entity pairs
+/// that have intersecting AABBs, with the smaller [`Entity`] first.
+///
+/// Only pairs where at least one of the intervals has changed are returned.
+///
+/// Sweep and prune exploits temporal coherence, as bodies are unlikely to move significantly between two simulation steps. Insertion sort is used, as it is good at sorting nearly sorted lists efficiently.
+///
+/// With the `parallel` feature, the sweep is split into chunks of intervals that are processed in parallel.
+/// The chunks are merged in order, so the resulting pairs are the same as with a single-threaded sweep.
+/// The sort and the merge are serial.
+fn sweep_and_prune(
+    intervals: &mut AabbIntervals,
+    exclusions: &CollisionExclusions,
+) -> Vec<(Entity, Entity)> {
+    // Sort bodies along the x-axis using insertion sort, a sorting algorithm great for sorting nearly sorted lists.
+    insertion_sort(&mut intervals.0, |a, b| a.2.min.x > b.2.min.x);
+
+    let intervals = &intervals.0;
+
+    #[cfg(feature = "parallel")]
+    let overlaps = {
+        let chunk_size = (intervals.len() / ComputeTaskPool::get().thread_num())
+            .max(MIN_PARALLEL_SWEEP_CHUNK_SIZE);
+        par_sweep_intervals(intervals, chunk_size)
+    };
+    #[cfg(not(feature = "parallel"))]
+    let overlaps = [sweep_intervals(intervals, 0..intervals.len())];
+
+    overlaps
+        .iter()
+        .flatten()
+        .filter_map(|&(i, j)| {
+            let (ent1, parent1, ..) = intervals[i];
+            let (ent2, parent2, ..) = intervals[j];
+
+            (!exclusions.excludes(ent1, parent1, ent2, parent2)).then(|| ordered_pair(ent1, ent2))
+        })
+        .collect()
+}
+
+/// The minimum number of intervals swept by a single task when the sweep is run in parallel.
+#[cfg(feature = "parallel")]
+const MIN_PARALLEL_SWEEP_CHUNK_SIZE: usize = 256;
+
+/// Sweeps the intervals in parallel in chunks of `chunk_size` intervals,
+/// and returns the indices of the intersecting intervals for each chunk in order.
+#[cfg(feature = "parallel")]
+fn par_sweep_intervals(intervals: &[AabbInterval], chunk_size: usize) -> Vec<Vec<(usize, usize)>> {
+    // Each chunk only tests its own intervals against the intervals after them,
+    // so the chunks can be swept independently.
+    intervals.par_chunk_map(ComputeTaskPool::get(), chunk_size, |chunk_index, chunk| {
+        let start = chunk_index * chunk_size;
+        sweep_intervals(intervals, start..start + chunk.len())
+    })
+}
+
+/// Finds the intervals that intersect the intervals in the given `range`, testing each interval
+/// only against the intervals after it. The intervals must be sorted by their minimum x extents.
+///
+/// Returns the indices of the intersecting intervals.
+fn sweep_intervals(intervals: &[AabbInterval], range: Range<usize>) -> Vec<(usize, usize)> {
+    let mut overlaps = Vec::new();
+
+    // Find potential collisions by checking for AABB intersections along all axes.
+    for i in range {
+        let (_, parent1, aabb1, layers1, _, inactive1, changed1) = &intervals[i];
+
+        for (j, (_, parent2, aabb2, layers2, _, inactive2, changed2)) in
+            intervals.iter().enumerate().skip(i + 1)
+        {
+            // x doesn't intersect; check this first so we can discard as soon as possible
+            if aabb2.min.x > aabb1.max.x {
+                break;
+            }
+
+            // The pairs of colliders that haven't changed are already up to date
+            if !*changed1 && !*changed2 {
+                continue;
+            }
+
+            // No collisions between bodies that haven't moved or colliders with incompatible layers or colliders with the same parent
+            if (*inactive1 && *inactive2) || !layers1.interacts_with(*layers2) || parent1 == parent2
+            {
+                continue;
+            }
+
+            // y doesn't intersect
+            if aabb1.min.y > aabb2.max.y || aabb1.max.y < aabb2.min.y {
+                continue;
+            }
+
+            #[cfg(feature = "3d")]
+            // z doesn't intersect
+            if aabb1.min.z > aabb2.max.z || aabb1.max.z < aabb2.min.z {
+                continue;
+            }
+
+            overlaps.push((i, j));
+        }
+    }
+
+    overlaps
+}
+
+/// Sorts a list iteratively using comparisons. In an ascending sort order, when a smaller value is encountered, it is moved lower in the list until it is larger than the item before it.
+///
+/// This is relatively slow for large lists, but very efficient in cases where the list is already mostly sorted.
+fn insertion_sort<T>(items: &mut [T], comparison: fn(&T, &T) -> bool) {
+    for i in 1..items.len() {
+        let mut j = i;
+        while j > 0 && comparison(&items[j - 1], &items[j]) {
+            items.swap(j - 1, j);
+            j -= 1;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::tests::{ball_collider, create_app, tick_60_fps};
+    #[cfg(feature = "parallel")]
+    use bevy::tasks::TaskPool;
+
+    #[cfg(all(
+        feature = "default-collider",
+        any(feature = "parry-f32", feature = "parry-f64")
+    ))]
+    #[test]
+    fn broad_phase_tracks_pair_changes() {
+        let mut app = create_app();
+
+        app.insert_resource(Gravity::ZERO);
+
+        let static_body = app
+            .world_mut()
+            .spawn((RigidBody::Static, ball_collider(0.5)))
+            .id();
+        let dynamic_body = app
+            .world_mut()
+            .spawn((RigidBody::Dynamic, Sensor, ball_collider(0.5)))
+            .id();
+
+        let pair = if static_body < dynamic_body {
+            (static_body, dynamic_body)
+        } else {
+            (dynamic_body, static_body)
+        };
+
+        tick_60_fps(&mut app);
+
+        let pair_changes = app.world().resource::<BroadCollisionPairChanges>();
+        assert_eq!(pair_changes.started(), &[pair]);
+        assert!(pair_changes.ended().is_empty());
+        assert!(pair_changes.contains(dynamic_body, static_body));
+
+        tick_60_fps(&mut app);
+
+        let pair_changes = app.world().resource::<BroadCollisionPairChanges>();
+        assert!(pair_changes.started().is_empty());
+        assert!(pair_changes.ended().is_empty());
+        assert_eq!(pair_changes.len(), 1);
+
+        // Move the dynamic body away from the static body.
+        app.world_mut().get_mut::<Position>(dynamic_body).unwrap().0 = Vector::X * 10.0;
+
+        tick_60_fps(&mut app);
+
+        let pair_changes = app.world().resource::<BroadCollisionPairChanges>();
+        assert!(pair_changes.started().is_empty());
+        assert_eq!(pair_changes.ended(), &[pair]);
+        assert!(pair_changes.is_empty());
+
+        // Move the dynamic body back, and despawn the static body.
+        app.world_mut().get_mut::<Position>(dynamic_body).unwrap().0 = Vector::ZERO;
+
+        tick_60_fps(&mut app);
+
+        let pair_changes = app.world().resource::<BroadCollisionPairChanges>();
+        assert_eq!(pair_changes.started(), &[pair]);
+        let collisions = app.world().resource::<Collisions>();
+        assert!(collisions.contains(static_body, dynamic_body));
+
+        // The narrow phase ends the collisions of pairs that stopped overlapping.
+        app.world_mut().despawn(static_body);
+
+        tick_60_fps(&mut app);
+
+        let pair_changes = app.world().resource::<BroadCollisionPairChanges>();
+        assert_eq!(pair_changes.ended(), &[pair]);
+        assert!(pair_changes.is_empty());
+        let collisions = app.world().resource::<Collisions>();
+        assert!(!collisions.contains(static_body, dynamic_body));
+    }
+
+    #[cfg(all(
+        feature = "default-collider",
+        any(feature = "parry-f32", feature = "parry-f64")
+    ))]
+    #[test]
+    fn broad_phase_updates_pairs_of_changed_colliders() {
+        let mut app = create_app();
+
+        app.insert_resource(Gravity::ZERO);
+
+        // None of the colliders move, so only their layers and exclusions change the pairs.
+        let static_body = app
+            .world_mut()
+            .spawn((RigidBody::Static, ball_collider(0.5)))
+            .id();
+        let [body1, body2] = [Vector::X * 0.5, Vector::NEG_X * 0.6].map(|position| {
+            app.world_mut()
+                .spawn((RigidBody::Kinematic, Position(position), ball_collider(0.5)))
+                .id()
+        });
+
+        // Returns the sorted pairs, checking that both resources contain the same pairs.
+        let sorted_pairs = |app: &App| {
+            let pair_changes = app.world().resource::<BroadCollisionPairChanges>();
+            let mut pairs = app.world().resource::<BroadCollisionPairs>().0.clone();
+            let mut tracked_pairs: Vec<_> = pair_changes.iter().copied().collect();
+            pairs.sort();
+            tracked_pairs.sort();
+            assert_eq!(pairs, tracked_pairs);
+            pairs
+        };
+
+        tick_60_fps(&mut app);
+
+        let pair1 = ordered_pair(static_body, body1);
+        let pair2 = ordered_pair(static_body, body2);
+        let mut expected_pairs = vec![pair1, pair2];
+        expected_pairs.sort();
+        assert_eq!(sorted_pairs(&app), expected_pairs);
+
+        // The first body no longer interacts with the static body.
+        app.world_mut()
+            .entity_mut(body1)
+            .insert(CollisionLayers::NONE);
+
+        tick_60_fps(&mut app);
+
+        let pair_changes = app.world().resource::<BroadCollisionPairChanges>();
+        assert_eq!(pair_changes.ended(), &[pair1]);
+        assert_eq!(sorted_pairs(&app), vec![pair2]);
+
+        // Excluding the other pair ends it too.
+        app.world_mut()
+            .resource_mut::<CollisionExclusions>()
+            .insert(static_body, body2);
+
+        tick_60_fps(&mut app);
+
+        let pair_changes = app.world().resource::<BroadCollisionPairChanges>();
+        assert_eq!(pair_changes.ended(), &[pair2]);
+        assert!(sorted_pairs(&app).is_empty());
+
+        // Nothing changed, so no pairs start or end.
+        tick_60_fps(&mut app);
+
+        let pair_changes = app.world().resource::<BroadCollisionPairChanges>();
+        assert!(pair_changes.started().is_empty());
+        assert!(pair_changes.ended().is_empty());
+
+        // Removing the exclusion and the layers starts both pairs again.
+        app.world_mut()
+            .resource_mut::<CollisionExclusions>()
+            .remove(static_body, body2);
+        app.world_mut()
+            .entity_mut(body1)
+            .remove::<CollisionLayers>();
+
+        tick_60_fps(&mut app);
+
+        let pair_changes = app.world().resource::<BroadCollisionPairChanges>();
+        assert_eq!(pair_changes.started().len(), 2);
+        assert_eq!(sorted_pairs(&app), expected_pairs);
+        let collisions = app.world().resource::<Collisions>();
+        assert!(collisions.contains(static_body, body1));
+        assert!(collisions.contains(static_body, body2));
+
+        // Despawning a collider removes its pair, and the remaining pair is kept in sync.
+        app.world_mut().despawn(body1);
+
+        tick_60_fps(&mut app);
+
+        let pair_changes = app.world().resource::<BroadCollisionPairChanges>();
+        assert_eq!(pair_changes.ended(), &[pair1]);
+        assert_eq!(sorted_pairs(&app), vec![pair2]);
+    }
+
+    #[cfg(feature = "parallel")]
+    #[test]
+    fn parallel_sweep_matches_serial_sweep() {
+        ComputeTaskPool::get_or_init(TaskPool::default);
+
+        // Scatter overlapping AABBs of different sizes using a simple deterministic LCG.
+        let mut seed: u32 = 12345;
+        let mut random = move || {
+            seed = seed.wrapping_mul(1_664_525).wrapping_add(1_013_904_223);
+            (seed >> 8) as Scalar / (1 << 24) as Scalar
+        };
+
+        let mut intervals: Vec<AabbInterval> = (0..1000)
+            .map(|i| {
+                let entity = Entity::from_raw(i);
+                #[cfg(feature = "2d")]
+                let center = Vector::new(random(), random()) * 50.0;
+                #[cfg(feature = "3d")]
+                let center = Vector::new(random(), random(), random()) * 50.0;
+                let half_size = Vector::splat(0.5 + random());
+                (
+                    entity,
+                    ColliderParent(entity),
+                    ColliderAabb::new(center, half_size),
+                    CollisionLayers::default(),
+                    false,
+                    false,
+                    true,
+                )
+            })
+            .collect();
+        insertion_sort(&mut intervals, |a, b| a.2.min.x > b.2.min.x);
+
+        let to_pairs = |overlaps: &[(usize, usize)]| -> Vec<(Entity, Entity)> {
+            overlaps
+                .iter()
+                .map(|&(i, j)| ordered_pair(intervals[i].0, intervals[j].0))
+                .collect()
+        };
+
+        let serial_pairs = to_pairs(&sweep_intervals(&intervals, 0..intervals.len()));
+        assert!(!serial_pairs.is_empty());
+
+        // The pairs must be the same and in the same order regardless of how the sweep is split.
+        for chunk_size in [1, 7, 64, 999, 1000, 2000] {
+            let parallel_overlaps: Vec<(usize, usize)> =
+                par_sweep_intervals(&intervals, chunk_size)
+                    .into_iter()
+                    .flatten()
+                    .collect();
+            assert_eq!(
+                to_pairs(&parallel_overlaps),
+                serial_pairs,
+                "chunk size {chunk_size}"
+            );
+        }
+    }
+}
@@ -0,0 +1,360 @@
+//! A broad phase that uses a uniform spatial hash grid.
+//!
+//! See [`SpatialHashBroadPhasePlugin`].
+
+use super::{
+    configure_broad_phase_sets, init_collision_exclusions, init_pair_tracking, AabbIntersections,
+    BroadPhaseSet, IsBodyInactive, StoreAabbIntersections,
+};
+use crate::prelude::*;
+use bevy::{prelude::*, utils::HashMap};
+
+/// Collects pairs of potentially colliding entities into [`BroadCollisionPairs`] using
+/// a uniform grid of cells that is stored in a hash map.
+///
+/// Each collider is inserted into all cells that its [`ColliderAabb`] overlaps,
+/// and only colliders that share a cell are tested against each other. This works best
+/// for worlds with many colliders of roughly the same size, where the cell size
+/// can be chosen to match the size of a typical collider.
+///
+/// The plugin produces the same [`BroadCollisionPairs`] and [`AabbIntersections`](super::AabbIntersections)
+/// as the [`BroadPhasePlugin`], so it can be used as a drop-in replacement:
+///
+/// ```no_run
+#[cfg_attr(feature = "2d", doc = "use avian2d::prelude::*;")]
+#[cfg_attr(feature = "3d", doc = "use avian3d::prelude::*;")]
+/// use bevy::prelude::*;
+///
+/// fn main() {
+///     App::new()
+///         .add_plugins((
+///             DefaultPlugins,
+///             PhysicsPlugins::default()
+///                 .build()
+///                 .disable::<BroadPhasePlugin>()
+///                 .add(SpatialHashBroadPhasePlugin),
+///         ))
+///         .insert_resource(SpatialHashBroadPhaseConfig { cell_size: 2.0 })
+///         .run();
+/// }
+/// ```
+///
+/// The grid can be configured using the [`SpatialHashBroadPhaseConfig`] resource.
+///
+/// The broad phase systems run in [`PhysicsStepSet::BroadPhase`].
+pub struct SpatialHashBroadPhasePlugin;
+
+impl Plugin for SpatialHashBroadPhasePlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<BroadCollisionPairs>()
+            .init_resource::<SpatialHashBroadPhaseConfig>()
+            .init_resource::<SpatialHashGrid>()
+            .register_type::<SpatialHashBroadPhaseConfig>();
+
+        configure_broad_phase_sets(app);
+        init_collision_exclusions(app);
+        init_pair_tracking(app);
+
+        let physics_schedule = app
+            .get_schedule_mut(PhysicsSchedule)
+            .expect("add PhysicsSchedule first");
+
+        physics_schedule
+            .add_systems(update_spatial_hash_grid.in_set(BroadPhaseSet::UpdateStructures));
+
+        physics_schedule
+            .add_systems(collect_collision_pairs.in_set(BroadPhaseSet::CollectCollisions));
+    }
+}
+
+/// A resource for configuring the [`SpatialHashBroadPhasePlugin`].
+#[derive(Resource, Reflect, Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serialize", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serialize", reflect(Serialize, Deserialize))]
+#[reflect(Debug, Resource, PartialEq)]
+pub struct SpatialHashBroadPhaseConfig {
+    /// The width of the cells in the grid.
+    ///
+    /// For the best performance, this should be roughly the size of a typical collider.
+    /// Colliders that are much larger than the cells overlap many cells, and colliders that
+    /// are much smaller than the cells are tested against more colliders than necessary.
+    ///
+    /// This is implicitly scaled by the [`PhysicsLengthUnit`].
+    ///
+    /// Default: `1.0`
+    pub cell_size: Scalar,
+}
+
+impl Default for SpatialHashBroadPhaseConfig {
+    fn default() -> Self {
+        Self { cell_size: 1.0 }
+    }
+}
+
+/// The maximum number of cells that a collider can be inserted into.
+/// Colliders that overlap more cells are tested against all other colliders instead.
+const MAX_CELLS_PER_PROXY: i64 = 64;
+
+/// The integer coordinates of a cell in the grid.
+#[cfg(feature = "2d")]
+type GridCell = IVec2;
+/// The integer coordinates of a cell in the grid.
+#[cfg(feature = "3d")]
+type GridCell = IVec3;
+
+/// The data of a collider stored in the [`SpatialHashGrid`].
+#[derive(Clone, Debug)]
+struct GridProxy {
+    entity: Entity,
+    parent: ColliderParent,
+    aabb: ColliderAabb,
+    layers: CollisionLayers,
+    store_intersections: StoreAabbIntersections,
+    is_inactive: IsBodyInactive,
+    /// The cell containing the minimum corner of the AABB.
+    min_cell: GridCell,
+    /// The cell containing the maximum corner of the AABB.
+    max_cell: GridCell,
+    /// True if the collider overlaps too many cells to be inserted into the grid.
+    is_large: bool,
+}
+
+/// A uniform grid of colliders, rebuilt every physics step.
+#[derive(Resource, Default)]
+struct SpatialHashGrid {
+    proxies: Vec<GridProxy>,
+    /// The indices of the proxies in each occupied cell.
+    cells: HashMap<GridCell, Vec<u32>>,
+    /// The indices of the proxies that overlap too many cells to be inserted into the grid.
+    large_proxies: Vec<u32>,
+}
+
+/// Returns the cell that contains the given point.
+fn cell_at(point: Vector, cell_size: Scalar) -> GridCell {
+    let cell = (point / cell_size).floor();
+    #[cfg(feature = "2d")]
+    {
+        cell.as_ivec2()
+    }
+    #[cfg(feature = "3d")]
+    {
+        cell.as_ivec3()
+    }
+}
+
+/// Returns the number of cells in the range from `min` to `max`, inclusive.
+fn cell_count(min: GridCell, max: GridCell) -> i64 {
+    min.to_array()
+        .into_iter()
+        .zip(max.to_array())
+        .fold(1, |count, (min, max)| {
+            count.saturating_mul(max as i64 - min as i64 + 1)
+        })
+}
+
+/// Calls `f` for each cell in the range from `min` to `max`, inclusive.
+fn for_each_cell(min: GridCell, max: GridCell, mut f: impl FnMut(GridCell)) {
+    for x in min.x..=max.x {
+        for y in min.y..=max.y {
+            #[cfg(feature = "2d")]
+            f(GridCell::new(x, y));
+            #[cfg(feature = "3d")]
+            for z in min.z..=max.z {
+                f(GridCell::new(x, y, z));
+            }
+        }
+    }
+}
+
+/// Rebuilds the [`SpatialHashGrid`] from the [`ColliderAabb`]s.
+#[allow(clippy::type_complexity)]
+fn update_spatial_hash_grid(
+    aabbs: Query<(
+        Entity,
+        &ColliderAabb,
+        Option<&ColliderParent>,
+        Option<&CollisionLayers>,
+        Has<AabbIntersections>,
+        Has<Sleeping>,
+    )>,
+    rbs: Query<&RigidBody>,
+    mut grid: ResMut<SpatialHashGrid>,
+    config: Res<SpatialHashBroadPhaseConfig>,
+    length_unit: Res<PhysicsLengthUnit>,
+) {
+    let SpatialHashGrid {
+        proxies,
+        cells,
+        large_proxies,
+    } = &mut *grid;
+    let cell_size = length_unit.0 * config.cell_size;
+
+    proxies.clear();
+    large_proxies.clear();
+
+    // Keep the allocations of the cells that were occupied during the previous step,
+    // but remove cells that have been empty for a full step.
+    cells.retain(|_, indices| {
+        let was_occupied = !indices.is_empty();
+        indices.clear();
+        was_occupied
+    });
+
+    for (entity, aabb, parent, layers, store_intersections, is_sleeping) in &aabbs {
+        // Non-finite AABBs can not be inserted into the grid.
+        if !aabb.min.is_finite() || !aabb.max.is_finite() {
+            continue;
+        }
+
+        let parent = parent.map_or(ColliderParent(entity), |p| *p);
+        let is_static = rbs.get(parent.get()).is_ok_and(RigidBody::is_static);
+
+        let min_cell = cell_at(aabb.min, cell_size);
+        let max_cell = cell_at(aabb.max, cell_size);
+        let is_large = cell_count(min_cell, max_cell) > MAX_CELLS_PER_PROXY;
+
+        let index = proxies.len() as u32;
+
+        proxies.push(GridProxy {
+            entity,
+            parent,
+            aabb: *aabb,
+            layers: layers.map_or(CollisionLayers::default(), |layers| *layers),
+            store_intersections,
+            is_inactive: is_static || is_sleeping,
+            min_cell,
+            max_cell,
+            is_large,
+        });
+
+        if is_large {
+            large_proxies.push(index);
+        } else {
+            for_each_cell(min_cell, max_cell, |cell| {
+                cells.entry(cell).or_default().push(index);
+            });
+        }
+    }
+}
+
+/// Collects bodies that are potentially colliding by testing the colliders
+/// that share a cell in the [`SpatialHashGrid`].
+fn collect_collision_pairs(
+    grid: Res<SpatialHashGrid>,
+    exclusions: Res<CollisionExclusions>,
+    mut broad_collision_pairs: ResMut<BroadCollisionPairs>,
+    mut aabb_intersection_query: Query<&mut AabbIntersections>,
+) {
+    for mut intersections in &mut aabb_intersection_query {
+        intersections.clear();
+    }
+
+    broad_collision_pairs.clear();
+
+    let SpatialHashGrid {
+        proxies,
+        cells,
+        large_proxies,
+    } = &*grid;
+
+    for (index1, proxy1) in proxies.iter().enumerate() {
+        if proxy1.is_large {
+            continue;
+        }
+
+        for_each_cell(proxy1.min_cell, proxy1.max_cell, |cell| {
+            let Some(indices) = cells.get(&cell) else {
+                return;
+            };
+
+            for &index2 in indices {
+                // Each pair is found from both colliders, so only handle it once.
+                if index2 as usize <= index1 {
+                    continue;
+                }
+
+                let proxy2 = &proxies[index2 as usize];
+
+                // Colliders can share several cells, so only handle the pair
+                // in the first cell that they share.
+                if cell != proxy1.min_cell.max(proxy2.min_cell) {
+                    continue;
+                }
+
+                test_pair(
+                    proxy1,
+                    proxy2,
+                    &exclusions,
+                    &mut broad_collision_pairs.0,
+                    &mut aabb_intersection_query,
+                );
+            }
+        });
+    }
+
+    // Test the colliders that are too large for the grid against all other colliders.
+    for &large_index in large_proxies {
+        let proxy1 = &proxies[large_index as usize];
+
+        for (index2, proxy2) in proxies.iter().enumerate() {
+            // Pairs of large colliders are found from both colliders, so only handle them once.
+            if index2 == large_index as usize || (proxy2.is_large && index2 < large_index as usize)
+            {
+                continue;
+            }
+
+            test_pair(
+                proxy1,
+                proxy2,
+                &exclusions,
+                &mut broad_collision_pairs.0,
+                &mut aabb_intersection_query,
+            );
+        }
+    }
+}
+
+/// Adds the pair to the [`BroadCollisionPairs`] if the colliders can collide
+/// and their AABBs intersect.
+fn test_pair(
+    proxy1: &GridProxy,
+    proxy2: &GridProxy,
+    exclusions: &CollisionExclusions,
+    broad_collision_pairs: &mut Vec<(Entity, Entity)>,
+    aabb_intersection_query: &mut Query<&mut AabbIntersections>,
+) {
+    // No collisions between bodies that haven't moved or colliders with incompatible layers or colliders with the same parent
+    if (proxy1.is_inactive && proxy2.is_inactive)
+        || !proxy1.layers.interacts_with(proxy2.layers)
+        || proxy1.parent == proxy2.parent
+    {
+        return;
+    }
+
+    if !proxy1.aabb.intersects(&proxy2.aabb) {
+        return;
+    }
+
+    let (ent1, ent2) = (proxy1.entity, proxy2.entity);
+
+    if exclusions.excludes(ent1, proxy1.parent, ent2, proxy2.parent) {
+        return;
+    }
+
+    if ent1 < ent2 {
+        broad_collision_pairs.push((ent1, ent2));
+    } else {
+        broad_collision_pairs.push((ent2, ent1));
+    }
+
+    if proxy1.store_intersections {
+        if let Ok(mut intersections) = aabb_intersection_query.get_mut(ent1) {
+            intersections.push(ent2);
+        }
+    }
+    if proxy2.store_intersections {
+        if let Ok(mut intersections) = aabb_intersection_query.get_mut(ent2) {
+            intersections.push(ent1);
+        }
+    }
+}
@@ -10,6 +10,7 @@ use bevy::scene::SceneInstance;
 use bevy::{
     ecs::{intern::Interned, schedule::ScheduleLabel, system::SystemId},
     prelude::*,
+    tasks::AsyncComputeTaskPool,
 };
 
 /// A plugin for handling generic collider backend logic.
@@ -225,12 +226,24 @@ impl<C: ScalableCollider> Plugin for ColliderBackendPlugin<C> {
                 .ambiguous_with_all(),
         );
 
+        app.init_resource::<ColliderConstructorConfig>()
+            .init_resource::<ColliderCache>()
+            .add_event::<ColliderConstructed>()
+            .add_event::<AssetEvent<Mesh>>();
+
         app.add_systems(
             Update,
             (
-                init_collider_constructors,
-                init_collider_constructor_hierarchies,
-            ),
+                invalidate_cached_colliders,
+                // Finished constructions are handled first, so that tasks replaced
+                // by new constructors in the same frame are not discarded.
+                insert_constructed_colliders,
+                (
+                    init_collider_constructors,
+                    init_collider_constructor_hierarchies,
+                ),
+            )
+                .chain(),
         );
     }
 }
@@ -285,6 +298,8 @@ pub(crate) fn init_colliders<C: AnyCollider>(
 fn init_collider_constructors(
     mut commands: Commands,
     meshes: Res<Assets<Mesh>>,
+    config: Res<ColliderConstructorConfig>,
+    mut cache: ResMut<ColliderCache>,
     constructors: Query<(
         Entity,
         Option<&Handle<Mesh>>,
@@ -317,14 +332,30 @@ fn init_collider_constructors(
             None
         };
 
-        let collider = Collider::try_from_constructor(constructor.clone(), mesh);
-        if let Some(collider) = collider {
-            commands.entity(entity).insert(collider);
+        let task = construct_collider(constructor, mesh_handle, mesh, &config, &mut cache);
+
+        // Colliders constructed in the background are inserted by `insert_constructed_colliders`.
+        let result = if config.is_asynchronous(constructor) {
+            None
         } else {
-            error!(
-                "Tried to add a collider to entity {name} via {constructor:#?}, \
-                but the collider could not be generated from mesh {mesh:#?}. Skipping.",
-            );
+            task.0.get()
+        };
+
+        match result {
+            Some(Some(collider)) => {
+                commands.entity(entity).insert(collider.clone());
+            }
+            Some(None) => {
+                error!(
+                    "Tried to add a collider to entity {name} via {constructor:#?}, \
+                    but the collider could not be generated from mesh {mesh:#?}. Skipping.",
+                );
+            }
+            None => {
+                commands
+                    .entity(entity)
+                    .insert((task, ColliderConstructionPending));
+            }
         }
         commands.entity(entity).remove::<ColliderConstructor>();
     }
@@ -336,6 +367,8 @@ fn init_collider_constructors(
 fn init_collider_constructor_hierarchies(
     mut commands: Commands,
     meshes: Res<Assets<Mesh>>,
+    config: Res<ColliderConstructorConfig>,
+    mut cache: ResMut<ColliderCache>,
     #[cfg(feature = "bevy_scene")] scene_spawner: Res<SceneSpawner>,
     #[cfg(feature = "bevy_scene")] scenes: Query<&Handle<Scene>>,
     #[cfg(feature = "bevy_scene")] scene_instances: Query<&SceneInstance>,
@@ -409,23 +442,42 @@ fn init_collider_constructor_hierarchies(
                     None
                 };
 
-                let collider = Collider::try_from_constructor(constructor, mesh);
-
-                if let Some(collider) = collider {
-                    commands.entity(child_entity).insert((
-                        collider,
-                        collider_data
-                            .layers
-                            .unwrap_or(collider_constructor_hierarchy.default_layers),
-                        collider_data
-                            .density
-                            .unwrap_or(collider_constructor_hierarchy.default_density),
-                    ));
+                let layers = collider_data
+                    .layers
+                    .unwrap_or(collider_constructor_hierarchy.default_layers);
+                let density = collider_data
+                    .density
+                    .unwrap_or(collider_constructor_hierarchy.default_density);
+
+                let task = construct_collider(&constructor, handle, mesh, &config, &mut cache);
+
+                // Colliders constructed in the background are inserted by `insert_constructed_colliders`.
+                let result = if config.is_asynchronous(&constructor) {
+                    None
                 } else {
-                    error!(
-                        "Tried to add a collider to entity {pretty_name} via {collider_constructor_hierarchy:#?}, \
-                        but the collider could not be generated from mesh {mesh:#?}. Skipping.",
-                    );
+                    task.0.get()
+                };
+
+                match result {
+                    Some(Some(collider)) => {
+                        commands
+                            .entity(child_entity)
+                            .insert((collider.clone(), layers, density));
+                    }
+                    Some(None) => {
+                        error!(
+                            "Tried to add a collider to entity {pretty_name} via {collider_constructor_hierarchy:#?}, \
+                            but the collider could not be generated from mesh {mesh:#?}. Skipping.",
+                        );
+                    }
+                    None => {
+                        commands.entity(child_entity).insert((
+                            task,
+                            ColliderConstructionPending,
+                            layers,
+                            density,
+                        ));
+                    }
                 }
             }
         }
@@ -436,6 +488,112 @@ fn init_collider_constructor_hierarchies(
     }
 }
 
+/// Constructs a [`Collider`] from the given constructor, and returns the result of the construction.
+///
+/// If the construction is [expensive](ColliderConstructor::is_expensive) and [`ColliderConstructorConfig::asynchronous`]
+/// is `true`, the collider is constructed in the background on the [`AsyncComputeTaskPool`],
+/// and the result is only set once the construction is finished. The collider should then be inserted
+/// by [`insert_constructed_colliders`], even if the result is already available.
+///
+/// Colliders constructed from meshes are stored in the [`ColliderCache`] and reused
+/// for other entities with the same mesh and constructor.
+fn construct_collider(
+    constructor: &ColliderConstructor,
+    mesh_handle: Option<&Handle<Mesh>>,
+    mesh: Option<&Mesh>,
+    config: &ColliderConstructorConfig,
+    cache: &mut ColliderCache,
+) -> ColliderConstructionTask {
+    // Colliders are only cached if they are constructed from a mesh.
+    let mesh_id = mesh.and(mesh_handle).map(Handle::id);
+
+    if let Some(task) = mesh_id.and_then(|id| cache.get_task(id, constructor)) {
+        return task.clone();
+    }
+
+    let task = ColliderConstructionTask::default();
+
+    if config.is_asynchronous(constructor) {
+        let result = task.0.clone();
+        let constructor = constructor.clone();
+        // The mesh is cloned, because the task can outlive the mesh asset.
+        let mesh = mesh.cloned();
+        AsyncComputeTaskPool::get()
+            .spawn(async move {
+                let _ = result.set(Collider::try_from_constructor(constructor, mesh.as_ref()));
+            })
+            .detach();
+    } else {
+        let _ = task
+            .0
+            .set(Collider::try_from_constructor(constructor.clone(), mesh));
+    }
+
+    if let Some(id) = mesh_id {
+        cache.insert(id, constructor.clone(), task.clone());
+    }
+
+    task
+}
+
+/// Inserts [`Collider`]s that were constructed in the background once their construction is finished,
+/// and sends [`ColliderConstructed`] events for them.
+///
+/// If the entity got a [`Collider`] or a new [`ColliderConstructor`] while the construction was pending,
+/// the constructed collider is discarded.
+fn insert_constructed_colliders(
+    mut commands: Commands,
+    tasks: Query<(
+        Entity,
+        Option<&Name>,
+        &ColliderConstructionTask,
+        Has<Collider>,
+        Has<ColliderConstructor>,
+    )>,
+    mut constructed_events: EventWriter<ColliderConstructed>,
+) {
+    for (entity, name, task, has_collider, has_constructor) in &tasks {
+        if has_collider || has_constructor {
+            // The collider was replaced while it was being constructed.
+            commands
+                .entity(entity)
+                .remove::<(ColliderConstructionTask, ColliderConstructionPending)>();
+            continue;
+        }
+
+        let Some(collider) = task.0.get() else {
+            // The collider is still being constructed.
+            continue;
+        };
+
+        let mut entity_commands = commands.entity(entity);
+        entity_commands.remove::<(ColliderConstructionTask, ColliderConstructionPending)>();
+
+        if let Some(collider) = collider {
+            entity_commands.insert(collider.clone());
+            constructed_events.send(ColliderConstructed { entity });
+        } else {
+            let name = pretty_name(name, entity);
+            error!(
+                "Tried to add a collider to entity {name} in the background, \
+                but the collider could not be generated. Skipping.",
+            );
+        }
+    }
+}
+
+/// Removes cached colliders constructed from meshes that were modified or removed.
+fn invalidate_cached_colliders(
+    mut cache: ResMut<ColliderCache>,
+    mut mesh_events: EventReader<AssetEvent<Mesh>>,
+) {
+    for event in mesh_events.read() {
+        if let AssetEvent::Modified { id } | AssetEvent::Removed { id } = event {
+            cache.remove(*id);
+        }
+    }
+}
+
 fn pretty_name(name: Option<&Name>, entity: Entity) -> String {
     name.map(|n| n.to_string())
         .unwrap_or_else(|| format!("<unnamed entity {}>", entity.index()))
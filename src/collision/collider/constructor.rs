@@ -1,6 +1,7 @@
 use crate::prelude::*;
 use bevy::prelude::*;
 use bevy::utils::HashMap;
+use std::sync::{Arc, OnceLock};
 
 /// A component that will automatically generate [`Collider`]s on its descendants at runtime.
 /// The type of the generated collider can be specified using [`ColliderConstructor`].
@@ -22,6 +23,9 @@ use bevy::utils::HashMap;
 ///
 /// For inserting colliders on the same entity, use [`ColliderConstructor`].
 ///
+/// Colliders constructed from meshes are cached in the [`ColliderCache`], so spawning
+/// the same scene many times only constructs each collider once.
+///
 /// ## Caveats
 ///
 /// When a component has multiple ancestors with [`ColliderConstructorHierarchy`], the insertion order is undefined.
@@ -236,7 +240,8 @@ pub struct ColliderConstructorHierarchyConfig {
 /// A component that will automatically generate a [`Collider`] at runtime using [`Collider::try_from_constructor`].
 /// Enabling the `collider-from-mesh` feature activates support for computing the shape dynamically from the mesh attached to the same entity.
 ///
-/// Since [`Collider`] is not [`Reflect`], you can use this type to statically specify a collider's shape instead.
+/// Unlike a [`Collider`], which is reflected as an opaque serialized value, this type describes
+/// the collider's shape with plain data, which is convenient for specifying shapes in scenes and editors.
 ///
 /// This component will never override a pre-existing [`Collider`] component on the same entity.
 ///
@@ -449,8 +454,152 @@ impl ColliderConstructor {
             false
         }
     }
+
+    /// Returns `true` if constructing the collider can be expensive, for example because it
+    /// requires processing a mesh or computing a convex decomposition.
+    ///
+    /// If [`ColliderConstructorConfig::asynchronous`] is `true`, these colliders
+    /// are constructed in the background on the [`AsyncComputeTaskPool`](bevy::tasks::AsyncComputeTaskPool).
+    pub fn is_expensive(&self) -> bool {
+        self.requires_mesh()
+            || matches!(
+                self,
+                Self::Trimesh { .. }
+                    | Self::TrimeshWithConfig { .. }
+                    | Self::ConvexDecomposition { .. }
+                    | Self::ConvexDecompositionWithConfig { .. }
+                    | Self::ConvexHull { .. }
+            )
+    }
+}
+
+/// A resource for configuring how [`Collider`]s are generated from [`ColliderConstructor`]s
+/// and [`ColliderConstructorHierarchy`]s.
+#[derive(Resource, Clone, Debug, Default, PartialEq, Reflect)]
+#[cfg_attr(feature = "serialize", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serialize", reflect(Serialize, Deserialize))]
+#[reflect(Debug, Resource, Default, PartialEq)]
+pub struct ColliderConstructorConfig {
+    /// If `true`, colliders with [expensive](ColliderConstructor::is_expensive) constructors,
+    /// such as trimeshes and convex decompositions generated from meshes, are constructed
+    /// in the background on the [`AsyncComputeTaskPool`](bevy::tasks::AsyncComputeTaskPool)
+    /// instead of blocking the app.
+    ///
+    /// While a collider is being constructed, its entity has the [`ColliderConstructionPending`] component.
+    /// Once the construction is finished, the [`Collider`] is inserted, and a [`ColliderConstructed`] event is sent.
+    ///
+    /// This is opt-in, because the [`Collider`] is then no longer available
+    /// right after the [`ColliderConstructor`] has been processed.
+    ///
+    /// Default: `false`
+    pub asynchronous: bool,
+}
+
+impl ColliderConstructorConfig {
+    /// Returns `true` if a collider with the given constructor is constructed in the background.
+    pub fn is_asynchronous(&self, constructor: &ColliderConstructor) -> bool {
+        self.asynchronous && constructor.is_expensive()
+    }
+}
+
+/// A marker component for entities whose [`Collider`] is being constructed in the background.
+///
+/// The component is removed once the collider has been inserted or its construction has failed.
+/// See [`ColliderConstructorConfig::asynchronous`].
+#[derive(Component, Clone, Copy, Debug, Default, PartialEq, Eq, Reflect)]
+#[cfg_attr(feature = "serialize", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serialize", reflect(Serialize, Deserialize))]
+#[reflect(Debug, Component, Default, PartialEq)]
+pub struct ColliderConstructionPending;
+
+/// An event that is sent when a [`Collider`] that was constructed in the background
+/// has been inserted on an entity. See [`ColliderConstructorConfig::asynchronous`].
+#[derive(Event, Clone, Copy, Debug, PartialEq, Eq)]
+pub struct ColliderConstructed {
+    /// The entity that the collider was inserted on.
+    pub entity: Entity,
+}
+
+/// A cache for [`Collider`]s constructed from meshes by [`ColliderConstructor`]s
+/// and [`ColliderConstructorHierarchy`]s.
+///
+/// Colliders are cached per mesh and constructor, so entities that construct a collider
+/// from the same mesh in the same way, like instances of the same scene, share one
+/// [`SharedShape`](crate::parry::shape::SharedShape) instead of each computing their own.
+///
+/// Cached colliders are removed when their mesh asset is modified or removed.
+#[derive(Resource, Default)]
+pub struct ColliderCache {
+    colliders: HashMap<AssetId<Mesh>, Vec<(ColliderConstructor, ColliderConstructionTask)>>,
+}
+
+impl ColliderCache {
+    /// Returns the cached collider constructed from the given mesh with the given constructor.
+    ///
+    /// Returns `None` if there is no cached collider, if it is still being constructed,
+    /// or if it could not be generated.
+    pub fn get(
+        &self,
+        mesh: impl Into<AssetId<Mesh>>,
+        constructor: &ColliderConstructor,
+    ) -> Option<&Collider> {
+        self.get_task(mesh.into(), constructor)?.0.get()?.as_ref()
+    }
+
+    /// Removes all cached colliders constructed from the given mesh.
+    pub fn remove(&mut self, mesh: impl Into<AssetId<Mesh>>) {
+        self.colliders.remove(&mesh.into());
+    }
+
+    /// Removes all cached colliders.
+    pub fn clear(&mut self) {
+        self.colliders.clear();
+    }
+
+    /// Returns the number of cached colliders, including colliders that are still being constructed.
+    pub fn len(&self) -> usize {
+        self.colliders.values().map(Vec::len).sum()
+    }
+
+    /// Returns `true` if there are no cached colliders.
+    pub fn is_empty(&self) -> bool {
+        self.colliders.is_empty()
+    }
+
+    pub(crate) fn get_task(
+        &self,
+        mesh: AssetId<Mesh>,
+        constructor: &ColliderConstructor,
+    ) -> Option<&ColliderConstructionTask> {
+        self.colliders
+            .get(&mesh)?
+            .iter()
+            .find_map(|(cached_constructor, task)| {
+                (cached_constructor == constructor).then_some(task)
+            })
+    }
+
+    pub(crate) fn insert(
+        &mut self,
+        mesh: AssetId<Mesh>,
+        constructor: ColliderConstructor,
+        task: ColliderConstructionTask,
+    ) {
+        self.colliders
+            .entry(mesh)
+            .or_default()
+            .push((constructor, task));
+    }
 }
 
+/// The result of a background task constructing a [`Collider`] for an entity.
+///
+/// The result is set by the task once construction is finished. It is `None` if the collider
+/// could not be generated. A shared slot is used instead of polling the task itself,
+/// because tasks can't be polled on the single-threaded task pool.
+#[derive(Component, Clone, Default)]
+pub(crate) struct ColliderConstructionTask(pub(crate) Arc<OnceLock<Option<Collider>>>);
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -499,6 +648,135 @@ mod tests {
         assert!(app.query_err::<&ColliderConstructor>(entity));
     }
 
+    #[cfg(all(feature = "3d", feature = "collider-from-mesh"))]
+    #[test]
+    fn collider_constructor_converts_mesh_asynchronously() {
+        let mut app = create_test_app();
+        app.insert_resource(ColliderConstructorConfig { asynchronous: true });
+
+        let mesh_handle = app.add_mesh();
+        let entity = app
+            .world_mut()
+            .spawn((COMPUTED_COLLIDER.clone(), mesh_handle))
+            .id();
+
+        app.update();
+
+        assert!(app.query_err::<&ColliderConstructor>(entity));
+
+        // Wait for the background task to finish.
+        for _ in 0..1000 {
+            if app.query_ok::<&Collider>(entity) {
+                break;
+            }
+            assert!(app.query_ok::<&ColliderConstructionPending>(entity));
+            std::thread::sleep(std::time::Duration::from_millis(1));
+            app.update();
+        }
+
+        assert!(app.query_ok::<&Collider>(entity));
+        assert!(app.query_err::<&ColliderConstructionPending>(entity));
+
+        let events = app.world().resource::<Events<ColliderConstructed>>();
+        let mut reader = events.get_reader();
+        let constructed: Vec<_> = reader.read(events).map(|event| event.entity).collect();
+        assert_eq!(constructed, vec![entity]);
+    }
+
+    #[cfg(all(feature = "3d", feature = "collider-from-mesh"))]
+    #[test]
+    fn collider_constructor_keeps_colliders_inserted_while_pending() {
+        let mut app = create_test_app();
+        app.insert_resource(ColliderConstructorConfig { asynchronous: true });
+
+        let mesh_handle = app.add_mesh();
+        let entity = app
+            .world_mut()
+            .spawn((COMPUTED_COLLIDER.clone(), mesh_handle))
+            .id();
+
+        app.update();
+
+        // Replace the collider before the background task has been handled.
+        app.world_mut()
+            .entity_mut(entity)
+            .insert(Collider::sphere(1.0));
+
+        for _ in 0..1000 {
+            if app.query_err::<&ColliderConstructionPending>(entity) {
+                break;
+            }
+            std::thread::sleep(std::time::Duration::from_millis(1));
+            app.update();
+        }
+
+        assert!(app.query_err::<&ColliderConstructionPending>(entity));
+        let collider = app.world().get::<Collider>(entity).unwrap();
+        assert!(collider.shape().as_ball().is_some());
+
+        let events = app.world().resource::<Events<ColliderConstructed>>();
+        assert!(events.get_reader().read(events).next().is_none());
+    }
+
+    #[cfg(all(feature = "3d", feature = "collider-from-mesh"))]
+    #[test]
+    fn collider_constructor_reuses_cached_colliders() {
+        let mut app = create_test_app();
+
+        let mesh_handle = app.add_mesh();
+        let entity1 = app
+            .world_mut()
+            .spawn((COMPUTED_COLLIDER.clone(), mesh_handle.clone()))
+            .id();
+        let entity2 = app
+            .world_mut()
+            .spawn((COMPUTED_COLLIDER.clone(), mesh_handle.clone()))
+            .id();
+
+        app.update();
+
+        assert_eq!(app.world().resource::<ColliderCache>().len(), 1);
+        assert!(app
+            .world()
+            .resource::<ColliderCache>()
+            .get(&mesh_handle, &COMPUTED_COLLIDER)
+            .is_some());
+
+        let shape1 = app
+            .world()
+            .get::<Collider>(entity1)
+            .unwrap()
+            .shape()
+            .clone();
+        let shape2 = app
+            .world()
+            .get::<Collider>(entity2)
+            .unwrap()
+            .shape()
+            .clone();
+        assert!(Arc::ptr_eq(&shape1.0, &shape2.0));
+
+        // Modifying the mesh should invalidate the cached collider.
+        app.world_mut().send_event(AssetEvent::Modified {
+            id: mesh_handle.id(),
+        });
+        let entity3 = app
+            .world_mut()
+            .spawn((COMPUTED_COLLIDER.clone(), mesh_handle.clone()))
+            .id();
+
+        app.update();
+
+        let shape3 = app
+            .world()
+            .get::<Collider>(entity3)
+            .unwrap()
+            .shape()
+            .clone();
+        assert!(!Arc::ptr_eq(&shape1.0, &shape3.0));
+        assert_eq!(app.world().resource::<ColliderCache>().len(), 1);
+    }
+
     #[test]
     fn collider_constructor_hierarchy_does_nothing_on_self_with_primitive() {
         let mut app = create_test_app();
@@ -31,8 +31,10 @@ mod world_query;
 pub use world_query::*;
 
 mod constructor;
+pub(crate) use constructor::ColliderConstructionTask;
 pub use constructor::{
-    ColliderConstructor, ColliderConstructorHierarchy, ColliderConstructorHierarchyConfig,
+    ColliderCache, ColliderConstructed, ColliderConstructionPending, ColliderConstructor,
+    ColliderConstructorConfig, ColliderConstructorHierarchy, ColliderConstructorHierarchyConfig,
 };
 
 /// A trait for creating colliders from other types.
@@ -86,6 +88,25 @@ pub trait AnyCollider: Component {
         rotation2: impl Into<Rotation>,
         prediction_distance: Scalar,
     ) -> Vec<ContactManifold>;
+
+    /// Tests whether two colliders are intersecting each other.
+    ///
+    /// This is used for [sensors](Sensor) that don't need contact points. By default,
+    /// this computes the [contact manifolds](AnyCollider::contact_manifolds) and checks
+    /// if any of the contacts are penetrating, but implementors can override it with a faster test.
+    fn intersection_test(
+        &self,
+        other: &Self,
+        position1: Vector,
+        rotation1: impl Into<Rotation>,
+        position2: Vector,
+        rotation2: impl Into<Rotation>,
+    ) -> bool {
+        self.contact_manifolds(other, position1, rotation1, position2, rotation2, 0.0)
+            .iter()
+            .flat_map(|manifold| manifold.contacts.iter())
+            .any(|contact| contact.penetration >= 0.0)
+    }
 }
 
 /// A trait for colliders that support scaling.
@@ -229,6 +250,9 @@ impl From<Transform> for ColliderTransform {
 ///
 /// Sensor colliders do *not* contribute to the mass properties of rigid bodies.
 ///
+/// By default, sensors only test whether they are intersecting other colliders, and don't compute
+/// contact points. To get the [contact manifolds](ContactManifold) of sensor collisions, add the [`SensorContacts`] component.
+///
 /// ## Example
 ///
 /// ```
@@ -256,6 +280,38 @@ impl From<Transform> for ColliderTransform {
 #[reflect(Debug, Component, Default, PartialEq)]
 pub struct Sensor;
 
+/// A component that makes a [`Sensor`] compute full [contact manifolds](ContactManifold)
+/// with the colliders it intersects.
+///
+/// By default, sensors only test whether they are intersecting other colliders,
+/// which is much cheaper than computing contacts. The [`Contacts`] of sensor collisions
+/// then have no manifolds. Add this component to sensors that need contact points and normals.
+///
+/// ## Example
+///
+/// ```
+#[cfg_attr(feature = "2d", doc = "use avian2d::prelude::*;")]
+#[cfg_attr(feature = "3d", doc = "use avian3d::prelude::*;")]
+/// use bevy::prelude::*;
+///
+/// fn setup(mut commands: Commands) {
+///     // Spawn a sensor that computes contact points with the colliders it intersects.
+#[cfg_attr(
+    feature = "2d",
+    doc = "    commands.spawn((RigidBody::Static, Collider::circle(0.5), Sensor, SensorContacts));"
+)]
+#[cfg_attr(
+    feature = "3d",
+    doc = "    commands.spawn((RigidBody::Static, Collider::sphere(0.5), Sensor, SensorContacts));"
+)]
+/// }
+/// ```
+#[derive(Reflect, Clone, Component, Debug, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "serialize", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serialize", reflect(Serialize, Deserialize))]
+#[reflect(Debug, Component, Default, PartialEq)]
+pub struct SensorContacts;
+
 /// The Axis-Aligned Bounding Box of a [collider](Collider).
 #[derive(Reflect, Clone, Copy, Component, Debug, PartialEq)]
 #[cfg_attr(feature = "serialize", derive(serde::Serialize, serde::Deserialize))]
@@ -428,6 +484,151 @@ impl Default for ColliderAabb {
 #[doc(alias = "ContactSkin")]
 pub struct CollisionMargin(pub Scalar);
 
+/// The velocity of the surface of a [`Collider`], expressed in the local space of the collider.
+///
+/// Friction drives the bodies in contact with the collider towards the velocity of its surface,
+/// so the surface can carry them along without the collider itself moving.
+/// This can be used for things like conveyor belts, treadmills, and escalators.
+///
+/// The component of the velocity along the contact normal is ignored.
+///
+/// # Example
+///
+/// ```
+#[cfg_attr(feature = "2d", doc = "use avian2d::{math::Vector, prelude::*};")]
+#[cfg_attr(feature = "3d", doc = "use avian3d::{math::Vector, prelude::*};")]
+/// use bevy::prelude::*;
+///
+/// fn setup(mut commands: Commands) {
+///     // Spawn a conveyor belt that carries objects along the X axis at 2 units per second.
+///     commands.spawn((
+///         RigidBody::Static,
+#[cfg_attr(feature = "2d", doc = "        Collider::rectangle(10.0, 0.5),")]
+#[cfg_attr(feature = "3d", doc = "        Collider::cuboid(10.0, 0.5, 2.0),")]
+///         SurfaceVelocity(Vector::X * 2.0),
+///     ));
+/// }
+/// ```
+#[derive(Reflect, Clone, Copy, Component, Debug, Default, Deref, DerefMut, PartialEq, From)]
+#[cfg_attr(feature = "serialize", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serialize", reflect(Serialize, Deserialize))]
+#[reflect(Debug, Component, Default, PartialEq)]
+#[doc(alias = "TangentVelocity")]
+pub struct SurfaceVelocity(pub Vector);
+
+/// Physics materials for the individual sub-shapes of a [`Collider`], such as the triangles
+/// of a [trimesh](Collider::trimesh) or [heightfield](Collider::heightfield), the segments
+/// of a [polyline](Collider::polyline), or the child shapes of a [compound](Collider::compound).
+///
+/// Each sub-shape is mapped to a [`SubShapeMaterial`] through a table of material indices,
+/// indexed by the [sub-shape index](Collider::subshape_index_at_point). When a contact manifold
+/// is created for a sub-shape with a material, its friction and restitution are used instead of
+/// the [`Friction`] and [`Restitution`] of the collider or its rigid body. Sub-shapes without an entry
+/// in the table, or with an index that doesn't refer to a material, fall back to the properties of the collider.
+///
+/// The material properties are resolved by the narrow phase, and the resulting coefficients
+/// are stored in each [`ContactManifold`]. They can still be changed using [`CollisionHooks`].
+///
+/// # Example
+///
+/// ```
+#[cfg_attr(feature = "2d", doc = "use avian2d::{math::Vector, prelude::*};")]
+#[cfg_attr(feature = "3d", doc = "use avian3d::{math::Vector, prelude::*};")]
+/// use bevy::prelude::*;
+///
+/// fn setup(mut commands: Commands) {
+///     let asphalt = SubShapeMaterial::new(Friction::new(0.9), Restitution::new(0.1));
+///     let ice = SubShapeMaterial::new(Friction::new(0.05), Restitution::new(0.0));
+///
+///     // A road made of two triangles, where the second triangle is icy.
+///     commands.spawn((
+///         RigidBody::Static,
+#[cfg_attr(
+    feature = "2d",
+    doc = "        Collider::trimesh(
+            vec![Vector::new(-5.0, 0.0), Vector::new(5.0, 0.0), Vector::new(5.0, -1.0), Vector::new(-5.0, -1.0)],
+            vec![[0, 1, 2], [0, 2, 3]],
+        ),"
+)]
+#[cfg_attr(
+    feature = "3d",
+    doc = "        Collider::trimesh(
+            vec![Vector::new(-5.0, 0.0, -5.0), Vector::new(5.0, 0.0, -5.0), Vector::new(5.0, 0.0, 5.0), Vector::new(-5.0, 0.0, 5.0)],
+            vec![[0, 2, 1], [0, 3, 2]],
+        ),"
+)]
+///         SubShapeMaterials::new(vec![asphalt, ice], vec![0, 1]),
+///     ));
+/// }
+/// ```
+#[derive(Reflect, Clone, Component, Debug, Default, PartialEq)]
+#[cfg_attr(feature = "serialize", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serialize", reflect(Serialize, Deserialize))]
+#[reflect(Debug, Component, Default, PartialEq)]
+pub struct SubShapeMaterials {
+    /// The materials that the sub-shapes can refer to.
+    pub materials: Vec<SubShapeMaterial>,
+    /// The index of the material in [`materials`](Self::materials) for each sub-shape,
+    /// indexed by the sub-shape index.
+    pub indices: Vec<u32>,
+}
+
+impl SubShapeMaterials {
+    /// Creates a new [`SubShapeMaterials`] component from the given `materials`
+    /// and the material index of each sub-shape.
+    pub fn new(materials: Vec<SubShapeMaterial>, indices: Vec<u32>) -> Self {
+        Self { materials, indices }
+    }
+
+    /// Creates a new [`SubShapeMaterials`] component for a 3D [heightfield](Collider::heightfield)
+    /// from the given `materials` and the material index of each cell.
+    ///
+    /// Each cell of the heightfield is split into two triangles that share the material of the cell.
+    /// The cell in row `i` and column `j` has the index `j * (rows - 1) + i`.
+    #[cfg(feature = "3d")]
+    pub fn from_heightfield_cells(
+        materials: Vec<SubShapeMaterial>,
+        cell_indices: Vec<u32>,
+    ) -> Self {
+        // The triangle indices of the second triangles of the cells follow the first triangles.
+        let indices = cell_indices
+            .iter()
+            .chain(cell_indices.iter())
+            .copied()
+            .collect();
+        Self { materials, indices }
+    }
+
+    /// Returns the material of the sub-shape with the given index, if it has one.
+    pub fn get(&self, subshape_index: u32) -> Option<&SubShapeMaterial> {
+        self.indices
+            .get(subshape_index as usize)
+            .and_then(|&index| self.materials.get(index as usize))
+    }
+}
+
+/// The physics material of a sub-shape of a [`Collider`]. See [`SubShapeMaterials`].
+#[derive(Reflect, Clone, Copy, Debug, Default, PartialEq)]
+#[cfg_attr(feature = "serialize", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serialize", reflect(Serialize, Deserialize))]
+#[reflect(Debug, Default, PartialEq)]
+pub struct SubShapeMaterial {
+    /// The friction of the sub-shape.
+    pub friction: Friction,
+    /// The restitution of the sub-shape.
+    pub restitution: Restitution,
+}
+
+impl SubShapeMaterial {
+    /// Creates a new [`SubShapeMaterial`] with the given friction and restitution.
+    pub fn new(friction: Friction, restitution: Restitution) -> Self {
+        Self {
+            friction,
+            restitution,
+        }
+    }
+}
+
 /// A component that stores the entities that are colliding with an entity.
 ///
 /// This component is automatically added for all entities with a [`Collider`],
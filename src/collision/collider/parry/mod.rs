@@ -6,7 +6,10 @@ use bevy::render::mesh::{Indices, VertexAttributeValues};
 use bevy::{log, prelude::*};
 use collision::contact_query::UnsupportedShape;
 use itertools::Either;
-use parry::shape::{RoundShape, SharedShape, TypedShape};
+use parry::{
+    query::{PointQuery, PointQueryWithLocation},
+    shape::{RoundShape, Shape, SharedShape, TypedShape},
+};
 
 #[cfg(feature = "2d")]
 mod primitives2d;
@@ -357,9 +360,18 @@ impl From<TrimeshFlags> for parry::shape::TriMeshFlags {
 /// To get a reference to the internal [`SharedShape`], you can use the [`Collider::shape()`]
 /// or [`Collider::shape_scaled()`] methods.
 ///
-/// `Collider` is currently not `Reflect`. If you need to reflect it, you can use [`ColliderConstructor`] as a workaround.
-#[derive(Clone, Component)]
+/// ## Serialization
+///
+/// With the `serialize` feature enabled, colliders with any of the built-in shapes can be serialized
+/// and deserialized with `serde`, including compound, trimesh and heightfield colliders.
+/// `Collider` is reflected as an opaque value that uses this serialized representation,
+/// so colliders can also be saved in and loaded from Bevy scenes.
+///
+/// Colliders with custom shapes that are not provided by `parry` cannot be deserialized.
+#[derive(Clone, Component, Reflect)]
 #[cfg_attr(feature = "serialize", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serialize", reflect_value(Serialize, Deserialize))]
+#[reflect_value(Debug, Component, Default)]
 pub struct Collider {
     /// The raw unscaled collider shape.
     shape: SharedShape,
@@ -370,6 +382,11 @@ pub struct Collider {
     scaled_shape: SharedShape,
     /// The global scale used for the collider shape.
     scale: Vector,
+    /// Whether the collider is a [chain](Collider::chain) that uses the neighboring segments
+    /// to fix contact normals at its internal vertices.
+    #[cfg(feature = "2d")]
+    #[cfg_attr(feature = "serialize", serde(default))]
+    is_chain: bool,
 }
 
 impl From<SharedShape> for Collider {
@@ -378,6 +395,8 @@ impl From<SharedShape> for Collider {
             shape: value.clone(),
             scaled_shape: value,
             scale: Vector::ONE,
+            #[cfg(feature = "2d")]
+            is_chain: false,
         }
     }
 }
@@ -482,6 +501,29 @@ impl AnyCollider for Collider {
             prediction_distance,
         )
     }
+
+    fn intersection_test(
+        &self,
+        other: &Self,
+        position1: Vector,
+        rotation1: impl Into<Rotation>,
+        position2: Vector,
+        rotation2: impl Into<Rotation>,
+    ) -> bool {
+        let rotation1: Rotation = rotation1.into();
+        let rotation2: Rotation = rotation2.into();
+
+        // Fall back to computing contacts for shape pairs that don't support intersection tests.
+        contact_query::intersection_test(self, position1, rotation1, other, position2, rotation2)
+            .unwrap_or_else(|_| {
+                contact_query::contact_manifolds(
+                    self, position1, rotation1, other, position2, rotation2, 0.0,
+                )
+                .iter()
+                .flat_map(|manifold| manifold.contacts.iter())
+                .any(|contact| contact.penetration >= 0.0)
+            })
+    }
 }
 
 impl ScalableCollider for Collider {
@@ -506,9 +548,16 @@ impl Collider {
     }
 
     /// Sets the unscaled shape of the collider. The collider's scale will be applied to this shape.
+    ///
+    /// If the collider was a [chain](Collider::chain), it is turned into a normal collider.
     pub fn set_shape(&mut self, shape: SharedShape) {
         self.shape = shape;
 
+        #[cfg(feature = "2d")]
+        {
+            self.is_chain = false;
+        }
+
         // TODO: The number of subdivisions probably shouldn't be hard-coded
         if let Ok(scaled) = scale_shape(&self.shape, self.scale, 10) {
             self.scaled_shape = scaled;
@@ -599,6 +648,42 @@ impl Collider {
             .contains_point(&make_isometry(translation, rotation), &point.into())
     }
 
+    /// Returns the index of the sub-shape of `self` transformed by `translation` and `rotation`
+    /// that is closest to the given `point`.
+    ///
+    /// - For [compound](Collider::compound) colliders, this is the index of the child shape.
+    /// - For [trimesh](Collider::trimesh) and 3D [heightfield](Collider::heightfield) colliders,
+    ///   this is the index of the triangle.
+    /// - For [polyline](Collider::polyline) and 2D [heightfield](Collider::heightfield) colliders,
+    ///   this is the index of the segment.
+    ///
+    /// Returns `None` for shapes that don't consist of sub-shapes.
+    pub fn subshape_index_at_point(
+        &self,
+        translation: impl Into<Position>,
+        rotation: impl Into<Rotation>,
+        point: Vector,
+    ) -> Option<u32> {
+        let isometry = make_isometry(translation, rotation);
+        subshape_index_at_local_point(
+            &**self.shape_scaled(),
+            isometry.inverse_transform_point(&point.into()).into(),
+        )
+    }
+
+    /// Returns `true` if the collider consists of sub-shapes that can be identified by an index,
+    /// such as the child shapes of a compound or the triangles of a trimesh.
+    /// See [`Collider::subshape_index_at_point`].
+    pub(crate) fn has_subshapes(&self) -> bool {
+        matches!(
+            self.shape_scaled().as_typed_shape(),
+            TypedShape::TriMesh(_)
+                | TypedShape::Polyline(_)
+                | TypedShape::HeightField(_)
+                | TypedShape::Compound(_)
+        )
+    }
+
     /// Computes the time of impact and normal between the given ray and `self`
     /// transformed by `translation` and `rotation`.
     ///
@@ -792,6 +877,64 @@ impl Collider {
         SharedShape::polyline(vertices, indices).into()
     }
 
+    /// Creates a collider with a chain shape defined by its vertices. Each vertex is connected to the next one
+    /// by a segment, and if `is_loop` is `true`, the last vertex is also connected to the first one.
+    ///
+    /// Unlike a [polyline](Collider::polyline), a chain uses the previous and next vertices of each segment
+    /// to fix the normals of contacts at its internal vertices. This prevents **ghost collisions**, where bodies
+    /// sliding across the chain snag on the vertices between segments. This makes chains well suited
+    /// for terrain and other static level geometry.
+    ///
+    /// Chains are one-sided, and only collide with shapes on the left side of each segment
+    /// relative to the order of the vertices. For ground defined from left to right, this is the upper side,
+    /// and for loops, a clockwise winding makes the outside solid. Shapes behind the chain pass through it.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use avian2d::{math::Vector, prelude::*};
+    /// use bevy::prelude::*;
+    ///
+    /// fn setup(mut commands: Commands) {
+    ///     // Boxes can slide across the flat ground without catching on the vertices.
+    ///     commands.spawn((
+    ///         RigidBody::Static,
+    ///         Collider::chain(
+    ///             vec![
+    ///                 Vector::new(-20.0, 2.0),
+    ///                 Vector::new(-10.0, 0.0),
+    ///                 Vector::new(0.0, 0.0),
+    ///                 Vector::new(10.0, 0.0),
+    ///                 Vector::new(20.0, 2.0),
+    ///             ],
+    ///             false,
+    ///         ),
+    ///     ));
+    /// }
+    /// ```
+    #[cfg(feature = "2d")]
+    pub fn chain(vertices: Vec<Vector>, is_loop: bool) -> Self {
+        let vertex_count = vertices.len() as u32;
+        let segment_count = if is_loop {
+            vertex_count
+        } else {
+            vertex_count.saturating_sub(1)
+        };
+        let indices = (0..segment_count)
+            .map(|i| [i, (i + 1) % vertex_count])
+            .collect();
+
+        let mut collider = Self::polyline(vertices, Some(indices));
+        collider.is_chain = true;
+        collider
+    }
+
+    /// Returns `true` if the collider is a [chain](Collider::chain).
+    #[cfg(feature = "2d")]
+    pub fn is_chain(&self) -> bool {
+        self.is_chain
+    }
+
     /// Creates a collider with a triangle mesh shape defined by its vertex and index buffers.
     ///
     /// Note that the resulting collider will be hollow and have no interior. This makes it more prone to tunneling and other collision issues.
@@ -1217,6 +1360,54 @@ impl Collider {
     }
 }
 
+/// Returns the index of the sub-shape of `shape` that is closest to the given `point`
+/// in the local space of the shape. See [`Collider::subshape_index_at_point`].
+pub(crate) fn subshape_index_at_local_point(shape: &dyn Shape, point: Vector) -> Option<u32> {
+    let point = point.into();
+
+    match shape.as_typed_shape() {
+        TypedShape::TriMesh(trimesh) => Some(
+            trimesh
+                .project_local_point_and_get_location(&point, false)
+                .1
+                 .0,
+        ),
+        TypedShape::Polyline(polyline) => Some(
+            polyline
+                .project_local_point_and_get_location(&point, false)
+                .1
+                 .0,
+        ),
+        TypedShape::HeightField(heightfield) => {
+            // Find the closest element among the ones near the point.
+            let tolerance = 1e-3 * (1.0 + Vector::from(point).abs().max_element());
+            let aabb = parry::bounding_volume::Aabb::from_half_extents(
+                point,
+                Vector::splat(tolerance).into(),
+            );
+            let mut closest = None;
+            heightfield.map_elements_in_local_aabb(&aabb, &mut |index, element| {
+                let distance = element.distance_to_local_point(&point, true);
+                if !matches!(closest, Some((_, closest_distance)) if closest_distance <= distance) {
+                    closest = Some((index, distance));
+                }
+            });
+            closest.map(|(index, _)| index)
+        }
+        TypedShape::Compound(compound) => compound
+            .shapes()
+            .iter()
+            .enumerate()
+            .map(|(index, (isometry, shape))| {
+                let local_point = isometry.inverse_transform_point(&point);
+                (index, shape.distance_to_local_point(&local_point, true))
+            })
+            .min_by(|(_, a), (_, b)| a.total_cmp(b))
+            .map(|(index, _)| index as u32),
+        _ => None,
+    }
+}
+
 #[cfg(all(feature = "3d", feature = "collider-from-mesh"))]
 type VerticesIndices = (Vec<nalgebra::Point3<Scalar>>, Vec<[u32; 3]>);
 
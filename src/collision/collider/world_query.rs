@@ -20,8 +20,11 @@ pub struct ColliderQuery<C: AnyCollider> {
     pub speculative_margin: Option<&'static SpeculativeMargin>,
     pub is_rb: Has<RigidBody>,
     pub is_sensor: Has<Sensor>,
+    pub has_sensor_contacts: Has<SensorContacts>,
     pub friction: Option<&'static Friction>,
     pub restitution: Option<&'static Restitution>,
+    pub surface_velocity: Option<&'static SurfaceVelocity>,
+    pub subshape_materials: Option<&'static SubShapeMaterials>,
     pub shape: &'static C,
 }
 
@@ -35,4 +38,12 @@ impl<'w, C: AnyCollider> ColliderQueryItem<'w, C> {
                 .as_ref()
                 .map_or_else(default, |t| t.0)
     }
+
+    /// Returns the [`SubShapeMaterial`] of the sub-shape with the given index,
+    /// if the collider has [`SubShapeMaterials`] and the sub-shape has a material.
+    pub fn subshape_material(&self, subshape_index: Option<u32>) -> Option<&SubShapeMaterial> {
+        self.subshape_materials
+            .zip(subshape_index)
+            .and_then(|(materials, index)| materials.get(index))
+    }
 }
@@ -116,6 +116,11 @@ pub fn contact(
 /// Returns an empty vector if the colliders are separated by a distance greater than `prediction_distance`
 /// or if the given shapes are invalid.
 ///
+/// The colliders have no material properties here, so the [`friction`](ContactManifold::friction),
+/// [`restitution`](ContactManifold::restitution) and [`tangent_velocity`](ContactManifold::tangent_velocity)
+/// of the returned manifolds are zero. The narrow phase fills them in from the colliders,
+/// rigid bodies and [`SubShapeMaterials`] of the contact pair.
+///
 /// ## Example
 ///
 /// ```
@@ -203,12 +208,20 @@ pub fn contact_manifolds(
                         -contact.dist,
                     )],
                     index: 0,
+                    subshape_index1: None,
+                    subshape_index2: None,
+                    // The materials are filled in by the narrow phase.
+                    friction: Friction::ZERO,
+                    restitution: Restitution::ZERO,
+                    tangent_velocity: Vector::ZERO,
                 }];
             }
         }
     }
 
     let mut manifold_index = 0;
+    let has_subshapes1 = collider1.has_subshapes();
+    let has_subshapes2 = collider2.has_subshapes();
 
     manifolds
         .iter()
@@ -249,8 +262,17 @@ pub fn contact_manifolds(
                     })
                     .collect(),
                 index: manifold_index,
+                subshape_index1: has_subshapes1.then_some(manifold.subshape1),
+                subshape_index2: has_subshapes2.then_some(manifold.subshape2),
+                // The materials are filled in by the narrow phase.
+                friction: Friction::ZERO,
+                restitution: Restitution::ZERO,
+                tangent_velocity: Vector::ZERO,
             };
 
+            #[cfg(feature = "2d")]
+            let manifold = fix_chain_manifold_normals(manifold, collider1, collider2, &isometry12)?;
+
             manifold_index += 1;
 
             Some(manifold)
@@ -258,6 +280,179 @@ pub fn contact_manifolds(
         .collect()
 }
 
+/// Fixes the normals of contacts against [chain](Collider::chain) colliders
+/// using the neighboring segments of the chain, preventing ghost collisions.
+///
+/// Chains are one-sided, so manifolds with shapes behind a segment are discarded, returning `None`.
+/// A contact normal at a vertex is only allowed if the vertex is convex and the normal
+/// is between the normals of the two segments sharing the vertex.
+/// Other normals are replaced by the normal of the segment.
+#[cfg(feature = "2d")]
+fn fix_chain_manifold_normals(
+    mut manifold: ContactManifold,
+    collider1: &Collider,
+    collider2: &Collider,
+    isometry12: &parry::math::Isometry<Scalar>,
+) -> Option<ContactManifold> {
+    if collider1.is_chain() {
+        if let (Some(polyline), Some(segment_index)) = (
+            collider1.shape_scaled().as_polyline(),
+            manifold.subshape_index1,
+        ) {
+            if !fix_chain_contact_normals(&mut manifold, polyline, segment_index, isometry12) {
+                return None;
+            }
+        }
+    }
+
+    if collider2.is_chain() {
+        if let (Some(polyline), Some(segment_index)) = (
+            collider2.shape_scaled().as_polyline(),
+            manifold.subshape_index2,
+        ) {
+            // Fix the normals with the chain as the first shape.
+            flip_manifold(&mut manifold);
+            let is_in_front = fix_chain_contact_normals(
+                &mut manifold,
+                polyline,
+                segment_index,
+                &isometry12.inverse(),
+            );
+            flip_manifold(&mut manifold);
+
+            if !is_in_front {
+                return None;
+            }
+        }
+    }
+
+    Some(manifold)
+}
+
+/// Fixes the normals of the contacts of the given `manifold` between the segment
+/// at `segment_index` of a chain and another shape.
+///
+/// The chain must be the first shape, and `isometry12` must transform
+/// from the local space of the other shape to the local space of the chain.
+///
+/// Returns `false` if the other shape is behind the segment, and the contacts should be ignored.
+#[cfg(feature = "2d")]
+fn fix_chain_contact_normals(
+    manifold: &mut ContactManifold,
+    chain: &parry::shape::Polyline,
+    segment_index: u32,
+    isometry12: &parry::math::Isometry<Scalar>,
+) -> bool {
+    // The tolerance for the dot products of the unit normals and directions.
+    const TOLERANCE: Scalar = 1e-4;
+
+    let indices = chain.indices();
+    let vertices = chain.vertices();
+    let segment_count = indices.len();
+    let index = segment_index as usize;
+
+    let Some([start_index, end_index]) = indices.get(index).copied() else {
+        return true;
+    };
+    let start = Vector::from(vertices[start_index as usize]);
+    let end = Vector::from(vertices[end_index as usize]);
+    let Some(direction) = (end - start).try_normalize() else {
+        return true;
+    };
+
+    // Chains are one-sided, and collide on the left side of each segment.
+    let face_normal = direction.perp();
+
+    // Ignore shapes that are behind the segment.
+    let origin2 = Vector::from(isometry12.translation.vector);
+    if (origin2 - start).dot(face_normal) < 0.0 {
+        return false;
+    }
+
+    let normal_alignment = manifold.normal1.dot(face_normal);
+
+    if normal_alignment >= 1.0 - TOLERANCE {
+        return true;
+    }
+
+    // Find the vertex that the contact is at. Contacts are only at vertices if the normal
+    // differs from the face normal. Normals pointing behind the segment are always replaced.
+    let Some(deepest) = manifold
+        .contacts
+        .iter()
+        .max_by(|a, b| a.penetration.total_cmp(&b.penetration))
+    else {
+        return true;
+    };
+    let at_start = deepest.point1.distance_squared(start) < deepest.point1.distance_squared(end);
+
+    // Find the direction of the neighboring segment that shares the vertex, pointing along the chain.
+    let neighbor_direction = if at_start {
+        let previous = indices[(index + segment_count - 1) % segment_count];
+        (segment_count > 1 && previous[1] == start_index)
+            .then(|| start - Vector::from(vertices[previous[0] as usize]))
+    } else {
+        let next = indices[(index + 1) % segment_count];
+        (segment_count > 1 && next[0] == end_index)
+            .then(|| Vector::from(vertices[next[1] as usize]) - end)
+    };
+
+    let is_valid = match neighbor_direction.and_then(|d| d.try_normalize()) {
+        Some(neighbor_direction) => {
+            // The vertex is convex if the neighboring segment bends away from the front side.
+            let is_convex = if at_start {
+                neighbor_direction.dot(face_normal) > TOLERANCE
+            } else {
+                neighbor_direction.dot(face_normal) < -TOLERANCE
+            };
+
+            // At a convex vertex, the normal can be anywhere between the normals of the two segments.
+            let neighbor_normal = neighbor_direction.perp();
+            let normal = manifold.normal1;
+            let cone_side = neighbor_normal.perp_dot(face_normal);
+            let in_cone = neighbor_normal.perp_dot(normal) * cone_side >= 0.0
+                && normal.perp_dot(face_normal) * cone_side >= 0.0;
+
+            is_convex && in_cone
+        }
+        // The ends of an open chain have no neighbors, so contacts in front of them are allowed.
+        None => normal_alignment > 0.0,
+    };
+
+    if is_valid {
+        return true;
+    }
+
+    // Use the normal of the segment, and project the contact points onto it.
+    let normal2 = -Vector::from(isometry12.inverse_transform_vector(&face_normal.into()));
+
+    manifold.normal1 = face_normal;
+    manifold.normal2 = normal2;
+
+    for contact in manifold.contacts.iter_mut() {
+        let point2 = Vector::from(isometry12.transform_point(&contact.point2.into()));
+        contact.penetration = (start - point2).dot(face_normal);
+        contact.point1 = point2 + face_normal * contact.penetration;
+        contact.normal1 = face_normal;
+        contact.normal2 = normal2;
+    }
+
+    true
+}
+
+/// Swaps the first and second shapes of the given `manifold`.
+#[cfg(feature = "2d")]
+fn flip_manifold(manifold: &mut ContactManifold) {
+    std::mem::swap(&mut manifold.normal1, &mut manifold.normal2);
+    std::mem::swap(&mut manifold.subshape_index1, &mut manifold.subshape_index2);
+
+    for contact in manifold.contacts.iter_mut() {
+        std::mem::swap(&mut contact.point1, &mut contact.point2);
+        std::mem::swap(&mut contact.normal1, &mut contact.normal2);
+        std::mem::swap(&mut contact.feature_id1, &mut contact.feature_id2);
+    }
+}
+
 /// Information about the closest points between two [`Collider`]s.
 ///
 /// The closest points can be computed using [`closest_points`].
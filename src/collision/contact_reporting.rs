@@ -2,10 +2,10 @@
 //!
 //! See [`ContactReportingPlugin`].
 
-use crate::prelude::*;
-use bevy::prelude::*;
+use crate::{dynamics::solver::ContactConstraints, prelude::*};
+use bevy::{ecs::entity::Entities, prelude::*};
 
-/// Sends collision events and updates [`CollidingEntities`].
+/// Sends collision events, triggers collision observers, and updates [`CollidingEntities`].
 ///
 /// ## Collision events
 ///
@@ -14,6 +14,7 @@ use bevy::prelude::*;
 ///
 /// - [`Collision`]
 /// - [`CollisionStarted`]
+/// - [`CollisionImpact`]
 /// - [`CollisionEnded`]
 ///
 /// You can listen to them with normal event readers:
@@ -40,22 +41,139 @@ use bevy::prelude::*;
 ///     }
 /// }
 /// ```
+///
+/// ## Collision observers
+///
+/// The [`OnCollisionStart`] and [`OnCollisionEnd`] events are also [triggered](Trigger) for the colliding entities,
+/// which allows individual entities to react to their own collisions using observers.
+/// The events are triggered for both colliders, and for the rigid bodies that they are attached to.
+///
+/// ```no_run
+#[cfg_attr(feature = "2d", doc = "use avian2d::prelude::*;")]
+#[cfg_attr(feature = "3d", doc = "use avian3d::prelude::*;")]
+/// use bevy::prelude::*;
+///
+/// #[derive(Component)]
+/// struct Player;
+///
+/// fn setup_pickup(mut commands: Commands) {
+///     commands
+///         .spawn((
+///             Sensor,
+#[cfg_attr(feature = "2d", doc = "            Collider::circle(0.5),")]
+#[cfg_attr(feature = "3d", doc = "            Collider::sphere(0.5),")]
+///         ))
+///         .observe(
+///             |trigger: Trigger<OnCollisionStart>, player_query: Query<&Player>, mut commands: Commands| {
+///                 // The player picks up the item when touching it.
+///                 let body = trigger.event().other_body.unwrap_or(trigger.event().other);
+///                 if player_query.contains(body) {
+///                     commands.entity(trigger.entity()).despawn();
+///                 }
+///             },
+///         );
+/// }
+/// ```
+///
+/// ## Opting in to collision events
+///
+/// By default, events are sent and observers are triggered for every pair of colliding entities.
+/// For worlds with a large number of contacts, this can be wasteful if only a few entities
+/// need to react to their collisions.
+///
+/// By setting [`ContactReportingConfig::events_enabled_by_default`] to `false`,
+/// collision events are only produced for pairs where at least one of the colliders,
+/// or one of the rigid bodies they are attached to, has the [`CollisionEventsEnabled`] component.
+/// [`CollidingEntities`] is still updated for all entities.
+///
+/// ## Contact force events
+///
+/// A [`ContactForceEvent`] is sent for pairs of colliders whose total contact force exceeds
+/// the [`ContactForceEventThreshold`] of either collider or its rigid body. This can be used
+/// for things like impact sounds and collision damage.
 pub struct ContactReportingPlugin;
 
 impl Plugin for ContactReportingPlugin {
     fn build(&self, app: &mut App) {
-        app.add_event::<Collision>()
+        app.init_resource::<ContactReportingConfig>()
+            .register_type::<(
+                ContactReportingConfig,
+                CollisionEventsEnabled,
+                ContactForceEventThreshold,
+            )>()
+            .add_event::<Collision>()
             .add_event::<CollisionStarted>()
-            .add_event::<CollisionEnded>();
+            .add_event::<CollisionImpact>()
+            .add_event::<CollisionEnded>()
+            .add_event::<ContactForceEvent>();
 
         let physics_schedule = app
             .get_schedule_mut(PhysicsSchedule)
             .expect("add PhysicsSchedule first");
 
-        physics_schedule.add_systems(report_contacts.in_set(PhysicsStepSet::ReportContacts));
+        physics_schedule.add_systems(
+            (report_contacts, report_contact_forces).in_set(PhysicsStepSet::ReportContacts),
+        );
+    }
+}
+
+/// A resource for configuring the [`ContactReportingPlugin`].
+#[derive(Resource, Reflect, Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serialize", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serialize", reflect(Serialize, Deserialize))]
+#[reflect(Debug, Resource, PartialEq)]
+pub struct ContactReportingConfig {
+    /// If `true`, [collision events](ContactReportingPlugin#collision-events) are sent
+    /// and [collision observers](ContactReportingPlugin#collision-observers) are triggered
+    /// for all colliding entities.
+    ///
+    /// If `false`, they are only produced for pairs where at least one of the colliders,
+    /// or one of the rigid bodies they are attached to, has the [`CollisionEventsEnabled`] component.
+    ///
+    /// Default: `true`
+    pub events_enabled_by_default: bool,
+}
+
+impl Default for ContactReportingConfig {
+    fn default() -> Self {
+        Self {
+            events_enabled_by_default: true,
+        }
     }
 }
 
+/// A marker component that enables [collision events](ContactReportingPlugin#collision-events)
+/// and [collision observers](ContactReportingPlugin#collision-observers) for a collider
+/// or for all colliders attached to a rigid body.
+///
+/// This only has an effect if [`ContactReportingConfig::events_enabled_by_default`] is `false`.
+///
+/// ## Example
+///
+/// ```
+#[cfg_attr(feature = "2d", doc = "use avian2d::prelude::*;")]
+#[cfg_attr(feature = "3d", doc = "use avian3d::prelude::*;")]
+/// use bevy::prelude::*;
+///
+/// fn setup(mut commands: Commands, mut config: ResMut<ContactReportingConfig>) {
+///     // Only report collisions for entities that opt in.
+///     config.events_enabled_by_default = false;
+///
+///     // A trigger volume that reports collisions.
+///     commands.spawn((
+///         Sensor,
+#[cfg_attr(feature = "2d", doc = "        Collider::circle(2.0),")]
+#[cfg_attr(feature = "3d", doc = "        Collider::sphere(2.0),")]
+///         CollisionEventsEnabled,
+///     ));
+/// }
+/// ```
+#[derive(Reflect, Clone, Copy, Component, Debug, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "serialize", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serialize", reflect(Serialize, Deserialize))]
+#[reflect(Debug, Component, Default, PartialEq)]
+pub struct CollisionEventsEnabled;
+
 /// A [collision event](ContactReportingPlugin#collision-events)
 /// that is sent for each collision.
 ///
@@ -118,6 +236,43 @@ pub struct Collision(pub Contacts);
 #[cfg_attr(feature = "serialize", derive(serde::Serialize, serde::Deserialize))]
 pub struct CollisionStarted(pub Entity, pub Entity);
 
+/// A [collision event](ContactReportingPlugin#collision-events)
+/// that is sent together with [`CollisionStarted`] when two entities start colliding.
+///
+/// The [`ContactImpact`] describes the relative motion of the bodies at the deepest contact point
+/// before the contact was solved, which is useful for things like impact sounds and particle effects.
+/// By the time the event is read, the [`LinearVelocity`] of the bodies has already been corrected by the solver.
+///
+/// ## Example
+///
+/// ```no_run
+#[cfg_attr(feature = "2d", doc = "use avian2d::prelude::*;")]
+#[cfg_attr(feature = "3d", doc = "use avian3d::prelude::*;")]
+/// use bevy::prelude::*;
+///
+/// fn main() {
+///     App::new()
+///         .add_plugins((DefaultPlugins, PhysicsPlugins::default()))
+///         .add_systems(Update, print_impacts)
+///         .run();
+/// }
+///
+/// fn print_impacts(mut collision_event_reader: EventReader<CollisionImpact>) {
+///     for CollisionImpact(entity1, entity2, impact) in collision_event_reader.read() {
+///         println!(
+///             "Entities {:?} and {:?} hit each other at {} with a speed of {}",
+///             entity1,
+///             entity2,
+///             impact.point,
+///             impact.approach_speed,
+///         );
+///     }
+/// }
+/// ```
+#[derive(Event, Clone, Copy, Debug, PartialEq)]
+#[cfg_attr(feature = "serialize", derive(serde::Serialize, serde::Deserialize))]
+pub struct CollisionImpact(pub Entity, pub Entity, pub ContactImpact);
+
 /// A [collision event](ContactReportingPlugin#collision-events)
 /// that is sent when two entities stop colliding.
 ///
@@ -149,22 +304,197 @@ pub struct CollisionStarted(pub Entity, pub Entity);
 #[cfg_attr(feature = "serialize", derive(serde::Serialize, serde::Deserialize))]
 pub struct CollisionEnded(pub Entity, pub Entity);
 
-/// Sends collision events and updates [`CollidingEntities`].
+/// A component that enables [`ContactForceEvent`]s for a collider or for all colliders
+/// attached to a rigid body, sent when the total normal force between two colliders exceeds
+/// the given threshold in Newtons.
+///
+/// If both colliders in a contact pair have a threshold, the smaller one is used.
+///
+/// ## Example
+///
+/// ```
+#[cfg_attr(feature = "2d", doc = "use avian2d::prelude::*;")]
+#[cfg_attr(feature = "3d", doc = "use avian3d::prelude::*;")]
+/// use bevy::prelude::*;
+///
+/// fn setup(mut commands: Commands) {
+///     // A crate that reports hard impacts.
+///     commands.spawn((
+///         RigidBody::Dynamic,
+#[cfg_attr(feature = "2d", doc = "        Collider::rectangle(1.0, 1.0),")]
+#[cfg_attr(feature = "3d", doc = "        Collider::cuboid(1.0, 1.0, 1.0),")]
+///         ContactForceEventThreshold(500.0),
+///     ));
+/// }
+///
+/// fn play_impact_sounds(mut contact_force_events: EventReader<ContactForceEvent>) {
+///     for event in contact_force_events.read() {
+///         println!(
+///             "Impact at {} with a force of {} N and a speed of {} m/s",
+///             event.contact_point,
+///             event.total_normal_force,
+///             event.impact_speed,
+///         );
+///     }
+/// }
+/// ```
+#[derive(Reflect, Clone, Copy, Component, Debug, Default, Deref, DerefMut, PartialEq)]
+#[cfg_attr(feature = "serialize", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serialize", reflect(Serialize, Deserialize))]
+#[reflect(Debug, Component, Default, PartialEq)]
+pub struct ContactForceEventThreshold(pub Scalar);
+
+/// An event that is sent when the total normal force between two colliders exceeds
+/// the [`ContactForceEventThreshold`] of either collider or its rigid body.
+///
+/// The forces are computed from the contact impulses of the last substep.
+/// See [`ContactForceEventThreshold`] for an example.
+#[derive(Event, Clone, Copy, Debug, PartialEq)]
+#[cfg_attr(feature = "serialize", derive(serde::Serialize, serde::Deserialize))]
+pub struct ContactForceEvent {
+    /// The first collider in the contact.
+    pub entity1: Entity,
+    /// The second collider in the contact.
+    pub entity2: Entity,
+    /// The rigid body of the first collider.
+    pub body_entity1: Entity,
+    /// The rigid body of the second collider.
+    pub body_entity2: Entity,
+    /// The sum of the normal forces at all contact points between the colliders.
+    pub total_normal_force: Scalar,
+    /// The largest normal force at a single contact point.
+    pub max_normal_force: Scalar,
+    /// The world-space contact point with the largest normal force,
+    /// on the surface of the first collider.
+    pub contact_point: Vector,
+    /// The world-space contact normal at the [`contact_point`](Self::contact_point),
+    /// pointing from the first collider towards the second collider.
+    pub normal: Vector,
+    /// The speed at which the bodies were approaching each other along the normal
+    /// at the [`contact_point`](Self::contact_point) before the contact was solved.
+    pub impact_speed: Scalar,
+}
+
+/// A [collision observer](ContactReportingPlugin#collision-observers) event
+/// that is triggered for an entity when it starts colliding with another entity.
+///
+/// The event is triggered for both colliders, and for the rigid bodies that they are attached to.
+///
+/// ## Example
+///
+/// ```
+#[cfg_attr(feature = "2d", doc = "use avian2d::prelude::*;")]
+#[cfg_attr(feature = "3d", doc = "use avian3d::prelude::*;")]
+/// use bevy::prelude::*;
+///
+/// fn spawn_trap(mut commands: Commands) {
+///     commands
+///         .spawn((
+///             RigidBody::Static,
+#[cfg_attr(feature = "2d", doc = "            Collider::rectangle(1.0, 0.2),")]
+#[cfg_attr(feature = "3d", doc = "            Collider::cuboid(1.0, 0.2, 1.0),")]
+///         ))
+///         .observe(|trigger: Trigger<OnCollisionStart>| {
+///             println!(
+///                 "{:?} stepped on the trap {:?}",
+///                 trigger.event().other,
+///                 trigger.entity(),
+///             );
+///         });
+/// }
+/// ```
+#[derive(Event, Clone, Copy, Debug, PartialEq)]
+#[cfg_attr(feature = "serialize", derive(serde::Serialize, serde::Deserialize))]
+pub struct OnCollisionStart {
+    /// The other collider that the entity started colliding with.
+    pub other: Entity,
+    /// The rigid body that the other collider is attached to, if any.
+    pub other_body: Option<Entity>,
+}
+
+/// A [collision observer](ContactReportingPlugin#collision-observers) event
+/// that is triggered for an entity when it stops colliding with another entity.
+///
+/// The event is triggered for both colliders, and for the rigid bodies that they are attached to.
+/// If a collider was removed or despawned, the event is not triggered for it.
+///
+/// ## Example
+///
+/// ```
+#[cfg_attr(feature = "2d", doc = "use avian2d::prelude::*;")]
+#[cfg_attr(feature = "3d", doc = "use avian3d::prelude::*;")]
+/// use bevy::prelude::*;
+///
+/// fn spawn_pressure_plate(mut commands: Commands) {
+///     commands
+///         .spawn((
+///             RigidBody::Static,
+#[cfg_attr(feature = "2d", doc = "            Collider::rectangle(1.0, 0.2),")]
+#[cfg_attr(feature = "3d", doc = "            Collider::cuboid(1.0, 0.2, 1.0),")]
+///         ))
+///         .observe(|trigger: Trigger<OnCollisionEnd>| {
+///             println!("{:?} left the pressure plate", trigger.event().other);
+///         });
+/// }
+/// ```
+#[derive(Event, Clone, Copy, Debug, PartialEq)]
+#[cfg_attr(feature = "serialize", derive(serde::Serialize, serde::Deserialize))]
+pub struct OnCollisionEnd {
+    /// The other collider that the entity stopped colliding with.
+    pub other: Entity,
+    /// The rigid body that the other collider is attached to, if any.
+    pub other_body: Option<Entity>,
+}
+
+/// Sends collision events, triggers collision observers, and updates [`CollidingEntities`].
 pub fn report_contacts(
+    mut commands: Commands,
     mut colliders: Query<&mut CollidingEntities>,
+    events_enabled_query: Query<(), With<CollisionEventsEnabled>>,
+    entities: &Entities,
+    config: Res<ContactReportingConfig>,
     collisions: Res<Collisions>,
     mut collision_ev_writer: EventWriter<Collision>,
     mut collision_started_ev_writer: EventWriter<CollisionStarted>,
+    mut collision_impact_ev_writer: EventWriter<CollisionImpact>,
     mut collision_ended_ev_writer: EventWriter<CollisionEnded>,
 ) {
     // TODO: Would batching events be worth it?
     for ((entity1, entity2), contacts) in collisions.get_internal().iter() {
+        // Only produce events for entities that have them enabled.
+        let events_enabled = config.events_enabled_by_default
+            || [
+                Some(contacts.entity1),
+                Some(contacts.entity2),
+                contacts.body_entity1,
+                contacts.body_entity2,
+            ]
+            .into_iter()
+            .flatten()
+            .any(|entity| events_enabled_query.contains(entity));
+
         if contacts.during_current_frame {
-            collision_ev_writer.send(Collision(contacts.clone()));
+            if events_enabled {
+                collision_ev_writer.send(Collision(contacts.clone()));
+            }
 
             // Collision started
             if !contacts.during_previous_frame {
-                collision_started_ev_writer.send(CollisionStarted(*entity1, *entity2));
+                if events_enabled {
+                    collision_started_ev_writer.send(CollisionStarted(*entity1, *entity2));
+
+                    if let Some(impact) = contacts.impact {
+                        collision_impact_ev_writer
+                            .send(CollisionImpact(*entity1, *entity2, impact));
+                    }
+
+                    trigger_collision_observers(
+                        &mut commands,
+                        entities,
+                        contacts,
+                        |other, other_body| OnCollisionStart { other, other_body },
+                    );
+                }
 
                 if let Ok(mut colliding_entities1) = colliders.get_mut(*entity1) {
                     colliding_entities1.insert(*entity2);
@@ -177,7 +507,16 @@ pub fn report_contacts(
 
         // Collision ended
         if !contacts.during_current_frame && contacts.during_previous_frame {
-            collision_ended_ev_writer.send(CollisionEnded(*entity1, *entity2));
+            if events_enabled {
+                collision_ended_ev_writer.send(CollisionEnded(*entity1, *entity2));
+
+                trigger_collision_observers(
+                    &mut commands,
+                    entities,
+                    contacts,
+                    |other, other_body| OnCollisionEnd { other, other_body },
+                );
+            }
 
             if let Ok(mut colliding_entities1) = colliders.get_mut(*entity1) {
                 colliding_entities1.remove(entity2);
@@ -188,3 +527,137 @@ pub fn report_contacts(
         }
     }
 }
+
+/// Triggers the event created by `event` for both colliders in `contacts`
+/// and for the rigid bodies that they are attached to.
+///
+/// The closure is given the other collider and its rigid body.
+fn trigger_collision_observers<E: Event>(
+    commands: &mut Commands,
+    entities: &Entities,
+    contacts: &Contacts,
+    event: impl Fn(Entity, Option<Entity>) -> E,
+) {
+    let mut trigger = |entity: Entity, body: Option<Entity>, other: Entity, other_body| {
+        // Skip entities that have been despawned.
+        let mut targets = Vec::with_capacity(2);
+        if entities.contains(entity) {
+            targets.push(entity);
+        }
+        if let Some(body) = body.filter(|&body| body != entity && entities.contains(body)) {
+            targets.push(body);
+        }
+        if !targets.is_empty() {
+            commands.trigger_targets(event(other, other_body), targets);
+        }
+    };
+
+    trigger(
+        contacts.entity1,
+        contacts.body_entity1,
+        contacts.entity2,
+        contacts.body_entity2,
+    );
+    trigger(
+        contacts.entity2,
+        contacts.body_entity2,
+        contacts.entity1,
+        contacts.body_entity1,
+    );
+}
+
+/// Sends [`ContactForceEvent`]s for contact pairs whose total normal force exceeds
+/// their [`ContactForceEventThreshold`].
+pub fn report_contact_forces(
+    constraints: Option<Res<ContactConstraints>>,
+    collisions: Res<Collisions>,
+    threshold_query: Query<&ContactForceEventThreshold>,
+    collider_query: Query<(&Position, &Rotation)>,
+    time: Res<Time<Substeps>>,
+    mut contact_force_ev_writer: EventWriter<ContactForceEvent>,
+) {
+    let Some(constraints) = constraints else {
+        return;
+    };
+
+    if threshold_query.is_empty() {
+        return;
+    }
+
+    let inv_delta_secs = (time.delta_seconds_f64() as Scalar).recip();
+
+    // Use the threshold of the collider, and fall back to the threshold of its body.
+    let threshold = |collider: Entity, body: Entity| {
+        threshold_query
+            .get(collider)
+            .or_else(|_| threshold_query.get(body))
+            .ok()
+            .map(|threshold| threshold.0)
+    };
+
+    // The constraints of the manifolds of a contact pair are stored next to each other.
+    for pair_constraints in constraints.chunk_by(|a, b| {
+        (a.collider_entity1, a.collider_entity2) == (b.collider_entity1, b.collider_entity2)
+    }) {
+        let first = &pair_constraints[0];
+
+        let threshold = match (
+            threshold(first.collider_entity1, first.entity1),
+            threshold(first.collider_entity2, first.entity2),
+        ) {
+            (Some(threshold1), Some(threshold2)) => threshold1.min(threshold2),
+            (Some(threshold), None) | (None, Some(threshold)) => threshold,
+            (None, None) => continue,
+        };
+
+        // Find the total normal impulse and the contact point with the largest impulse.
+        let mut total_normal_impulse = 0.0;
+        let mut max_normal_impulse = Scalar::MIN;
+        let mut max_point = None;
+
+        for constraint in pair_constraints {
+            for point in constraint.points.iter() {
+                let impulse = point.normal_part.impulse;
+                total_normal_impulse += impulse;
+
+                if impulse > max_normal_impulse {
+                    max_normal_impulse = impulse;
+                    max_point = Some((constraint, point));
+                }
+            }
+        }
+
+        let total_normal_force = total_normal_impulse * inv_delta_secs;
+
+        if total_normal_force <= threshold {
+            continue;
+        }
+
+        let Some((constraint, point)) = max_point else {
+            continue;
+        };
+
+        let Some(contact) = collisions
+            .get(constraint.collider_entity1, constraint.collider_entity2)
+            .and_then(|contacts| contacts.manifolds.get(constraint.manifold_index))
+            .and_then(|manifold| manifold.contacts.get(point.contact_index))
+        else {
+            continue;
+        };
+        let Ok((position1, rotation1)) = collider_query.get(constraint.collider_entity1) else {
+            continue;
+        };
+
+        contact_force_ev_writer.send(ContactForceEvent {
+            entity1: constraint.collider_entity1,
+            entity2: constraint.collider_entity2,
+            body_entity1: constraint.entity1,
+            body_entity2: constraint.entity2,
+            total_normal_force,
+            max_normal_force: max_normal_impulse * inv_delta_secs,
+            contact_point: contact.global_point1(position1, rotation1),
+            normal: constraint.normal,
+            impact_speed: -point.normal_speed,
+        });
+    }
+}
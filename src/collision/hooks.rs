@@ -0,0 +1,358 @@
+//! Collision hooks for filtering and modifying contacts.
+//!
+//! See [`CollisionHooks`].
+
+use crate::prelude::*;
+use bevy::ecs::{
+    entity::Entity,
+    system::{Commands, ReadOnlySystemParam},
+};
+
+/// A trait for user-defined hooks that can filter collision pairs and modify contacts computed by the narrow phase.
+///
+/// Collision hooks are registered for a [`NarrowPhasePlugin`] through its second generic parameter.
+///
+/// - [`filter_pairs`](Self::filter_pairs) is called by the narrow phase for each pair of colliders
+///   in [`BroadCollisionPairs`], before any contacts are computed for it. Rejected pairs never generate
+///   contact manifolds. If a colliding pair starts being rejected, its collision ends.
+/// - [`modify_contacts`](Self::modify_contacts) is called for each contact pair as soon as its [`Contacts`]
+///   have been computed, before [`PostProcessCollisions`] is run and before any
+///   [contact constraints](dynamics::solver::contact::ContactConstraint) are generated.
+///
+/// Filtering pairs is cheaper than removing their contacts afterwards, so rules like
+/// "projectiles ignore their shooter" should prefer [`filter_pairs`](Self::filter_pairs).
+/// [`CollisionLayers`] are still checked by the broad phase before the hooks are run.
+///
+/// Unlike systems in [`PostProcessCollisions`], hooks can change the material properties
+/// that the solver uses for each [`ContactManifold`] or contact point, such as the friction,
+/// restitution, and target tangent velocity.
+///
+/// The trait is implemented for a [`SystemParam`](bevy::ecs::system::SystemParam), so hooks can access
+/// resources and query for components. To allow the narrow phase to run in parallel,
+/// the system parameter must be read-only. Structural changes can be deferred using [`Commands`].
+///
+/// Tuples of hooks are also hooks. They run each hook in order, and stop as soon as one of them
+/// rejects the pair.
+///
+/// # Example
+///
+/// ```no_run
+#[cfg_attr(feature = "2d", doc = "use avian2d::prelude::*;")]
+#[cfg_attr(feature = "3d", doc = "use avian3d::prelude::*;")]
+/// use bevy::{ecs::system::SystemParam, prelude::*};
+///
+/// /// A component for surfaces that make everything that touches them slippery.
+/// #[derive(Component)]
+/// struct Ice;
+///
+/// #[derive(SystemParam)]
+/// struct IceHooks<'w, 's> {
+///     ice_query: Query<'w, 's, (), With<Ice>>,
+/// }
+///
+/// impl CollisionHooks for IceHooks<'_, '_> {
+///     fn modify_contacts(&self, contacts: &mut Contacts, _commands: &mut Commands) -> bool {
+///         if self.ice_query.contains(contacts.entity1) || self.ice_query.contains(contacts.entity2) {
+///             for manifold in contacts.manifolds.iter_mut() {
+///                 manifold.friction = Friction::ZERO;
+///             }
+///         }
+///
+///         // Keep the contacts.
+///         true
+///     }
+/// }
+///
+/// fn main() {
+///     App::new()
+///         .add_plugins((
+///             DefaultPlugins,
+///             PhysicsPlugins::default()
+///                 .build()
+///                 .disable::<NarrowPhasePlugin<Collider>>()
+///                 .add_after::<NarrowPhasePlugin<Collider>, _>(
+///                     NarrowPhasePlugin::<Collider, IceHooks>::default(),
+///                 ),
+///         ))
+///         .run();
+/// }
+/// ```
+pub trait CollisionHooks: ReadOnlySystemParam + Send + Sync {
+    /// Determines whether the colliders `collider1` and `collider2` found by the broad phase
+    /// should be checked for collisions. Returning `false` skips the pair for the current physics step,
+    /// so no [`Contacts`] are computed or reported for it.
+    ///
+    /// The pair is left in [`BroadCollisionPairs`], and the hooks are asked again on the next step.
+    /// This way, rules that change over time, such as "ignore for 0.5 s after spawn", take effect
+    /// even if the colliders don't move. The pair has already passed the checks of the broad phase,
+    /// such as [`CollisionLayers`].
+    ///
+    /// This is called in parallel for every collision pair found by the broad phase on every step,
+    /// right before [`modify_contacts`](Self::modify_contacts), so it should be fast.
+    #[allow(unused_variables)]
+    fn filter_pairs(&self, collider1: Entity, collider2: Entity, commands: &mut Commands) -> bool {
+        true
+    }
+
+    /// Modifies the [`Contacts`] between two colliders after they have been computed
+    /// by the narrow phase. Returning `false` removes the contact pair.
+    ///
+    /// The material properties of each [`ContactManifold`] have already been combined from
+    /// the [`Friction`] and [`Restitution`] of the colliders or their rigid bodies, and can be changed
+    /// for the whole manifold or overridden for individual [`ContactData`] points.
+    ///
+    /// This is called for every contact pair in parallel, so it should be fast.
+    #[allow(unused_variables)]
+    fn modify_contacts(&self, contacts: &mut Contacts, commands: &mut Commands) -> bool {
+        true
+    }
+}
+
+impl CollisionHooks for () {}
+
+macro_rules! impl_collision_hooks_for_tuple {
+    ($($hooks:ident),*) => {
+        #[allow(non_snake_case)]
+        impl<$($hooks: CollisionHooks),*> CollisionHooks for ($($hooks,)*) {
+            fn filter_pairs(&self, collider1: Entity, collider2: Entity, commands: &mut Commands) -> bool {
+                let ($($hooks,)*) = self;
+                $($hooks.filter_pairs(collider1, collider2, commands))&&*
+            }
+
+            fn modify_contacts(&self, contacts: &mut Contacts, commands: &mut Commands) -> bool {
+                let ($($hooks,)*) = self;
+                $($hooks.modify_contacts(contacts, commands))&&*
+            }
+        }
+    };
+}
+
+bevy::utils::all_tuples!(impl_collision_hooks_for_tuple, 1, 8, H);
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::tests::{
+        ball_collider, box_collider, create_app_with_hooks, spawn_box, spawn_floor, tick_60_fps,
+        tick_frames,
+    };
+    use approx::assert_relative_eq;
+    use bevy::prelude::*;
+
+    #[test]
+    fn collision_hooks_modify_contacts() {
+        use bevy::ecs::system::SystemParam;
+
+        #[derive(Component)]
+        struct Conveyor;
+
+        #[derive(Component)]
+        struct Ghost;
+
+        #[derive(SystemParam)]
+        struct TestHooks<'w, 's> {
+            conveyors: Query<'w, 's, (), With<Conveyor>>,
+            ghosts: Query<'w, 's, (), With<Ghost>>,
+        }
+
+        impl CollisionHooks for TestHooks<'_, '_> {
+            fn modify_contacts(&self, contacts: &mut Contacts, _commands: &mut Commands) -> bool {
+                if self.ghosts.contains(contacts.entity1) || self.ghosts.contains(contacts.entity2)
+                {
+                    return false;
+                }
+
+                // The tangent velocity is the velocity of the second body relative to the first body.
+                let direction = if self.conveyors.contains(contacts.entity1) {
+                    1.0
+                } else if self.conveyors.contains(contacts.entity2) {
+                    -1.0
+                } else {
+                    return true;
+                };
+
+                for manifold in contacts.manifolds.iter_mut() {
+                    manifold.tangent_velocity = direction * Vector::X * 2.0;
+                }
+
+                true
+            }
+        }
+
+        let mut app = create_app_with_hooks::<TestHooks>();
+
+        app.finish();
+
+        let floor = spawn_floor(&mut app, Vector::NEG_Y * 0.5, 100.0);
+        app.world_mut().entity_mut(floor).insert(Conveyor);
+        let carried_body = spawn_box(&mut app, Vector::Y * 0.5);
+        let ghost_body = spawn_box(&mut app, Vector::X * 10.0 + Vector::Y * 0.5);
+        app.world_mut().entity_mut(ghost_body).insert(Ghost);
+
+        tick_frames(&mut app, 60);
+
+        // The conveyor carries the body along its surface.
+        let velocity = app.world().get::<LinearVelocity>(carried_body).unwrap();
+        assert_relative_eq!(velocity.x, 2.0, epsilon = 0.1);
+        assert!(app
+            .world()
+            .resource::<Collisions>()
+            .contains(floor, carried_body));
+
+        // The contacts with the ghost are removed, so it falls through the floor.
+        let position = app.world().get::<Position>(ghost_body).unwrap();
+        assert!(position.y < -1.0);
+        assert!(!app
+            .world()
+            .resource::<Collisions>()
+            .contains(floor, ghost_body));
+    }
+
+    #[test]
+    fn collision_hooks_filter_pairs() {
+        use bevy::ecs::system::SystemParam;
+
+        /// The entity that fired a projectile.
+        #[derive(Component)]
+        struct Shooter(Entity);
+
+        #[derive(SystemParam)]
+        struct TestHooks<'w, 's> {
+            shooters: Query<'w, 's, &'static Shooter>,
+        }
+
+        impl CollisionHooks for TestHooks<'_, '_> {
+            fn filter_pairs(
+                &self,
+                collider1: Entity,
+                collider2: Entity,
+                _commands: &mut Commands,
+            ) -> bool {
+                // Projectiles ignore their shooter.
+                let ignores = |projectile: Entity, other: Entity| {
+                    self.shooters
+                        .get(projectile)
+                        .is_ok_and(|shooter| shooter.0 == other)
+                };
+                !ignores(collider1, collider2) && !ignores(collider2, collider1)
+            }
+        }
+
+        let mut app = create_app_with_hooks::<TestHooks>();
+
+        app.insert_resource(Gravity::ZERO);
+
+        app.finish();
+
+        let shooter = app
+            .world_mut()
+            .spawn((RigidBody::Static, box_collider(2.0, 2.0)))
+            .id();
+        app.world_mut().spawn((
+            RigidBody::Static,
+            Position(Vector::X * 5.0),
+            #[cfg(feature = "2d")]
+            Collider::rectangle(1.0, 10.0),
+            #[cfg(feature = "3d")]
+            Collider::cuboid(1.0, 10.0, 10.0),
+        ));
+        let projectile = app
+            .world_mut()
+            .spawn((
+                RigidBody::Dynamic,
+                Shooter(shooter),
+                LinearVelocity(Vector::X * 10.0),
+                ball_collider(0.25),
+            ))
+            .id();
+
+        for _ in 0..60 {
+            tick_60_fps(&mut app);
+
+            // The pair is rejected before any contacts are computed for it.
+            assert!(!app
+                .world()
+                .resource::<Collisions>()
+                .contains(shooter, projectile));
+        }
+
+        // The projectile leaves the shooter without being pushed out, and hits the wall.
+        let position = app.world().get::<Position>(projectile).unwrap();
+        assert!(position.x > 1.0 && position.x < 4.5);
+    }
+
+    #[test]
+    fn rejected_pairs_end_their_collisions() {
+        use bevy::ecs::system::SystemParam;
+
+        /// Rejects all collision pairs while `true`.
+        #[derive(Resource, Default)]
+        struct RejectPairs(bool);
+
+        #[derive(SystemParam)]
+        struct TestHooks<'w> {
+            reject_pairs: Res<'w, RejectPairs>,
+        }
+
+        impl CollisionHooks for TestHooks<'_> {
+            fn filter_pairs(&self, _: Entity, _: Entity, _: &mut Commands) -> bool {
+                !self.reject_pairs.0
+            }
+        }
+
+        let mut app = create_app_with_hooks::<TestHooks>();
+
+        app.insert_resource(Gravity::ZERO)
+            .init_resource::<RejectPairs>();
+        app.finish();
+
+        // Neither collider moves or changes, so only the hooks change whether the pair collides.
+        let static_body = app
+            .world_mut()
+            .spawn((RigidBody::Static, ball_collider(0.5)))
+            .id();
+        let kinematic_body = app
+            .world_mut()
+            .spawn((
+                RigidBody::Kinematic,
+                Position(Vector::X * 0.5),
+                ball_collider(0.5),
+                CollisionEventsEnabled,
+            ))
+            .id();
+
+        tick_60_fps(&mut app);
+        tick_60_fps(&mut app);
+
+        assert!(app
+            .world()
+            .resource::<Collisions>()
+            .contains(static_body, kinematic_body));
+
+        app.world_mut().resource_mut::<RejectPairs>().0 = true;
+
+        tick_60_fps(&mut app);
+
+        // The pair is still overlapping in the broad phase, but its collision has ended.
+        assert!(app
+            .world()
+            .resource::<BroadCollisionPairChanges>()
+            .contains(static_body, kinematic_body));
+        assert!(!app
+            .world()
+            .resource::<Collisions>()
+            .contains(static_body, kinematic_body));
+        let ended = app.world().resource::<Events<CollisionEnded>>();
+        assert_eq!(ended.get_reader().read(ended).count(), 1);
+
+        // Accepting the pair again starts a new collision without the colliders moving.
+        app.world_mut().resource_mut::<RejectPairs>().0 = false;
+
+        tick_60_fps(&mut app);
+
+        assert!(app
+            .world()
+            .resource::<Collisions>()
+            .contains(static_body, kinematic_body));
+    }
+}
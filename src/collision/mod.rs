@@ -11,6 +11,8 @@
 //! - [`NarrowPhasePlugin`]: Computes [`Contacts`] for each pair in [`BroadCollisionPairs`], adding them to [`Collisions`].
 //! - [`ContactReportingPlugin`] (optional): Sends collision events and updates [`CollidingEntities`] based on [`Collisions`].
 //!
+//! Collisions with [`OneWayPlatform`]s are filtered by the [`OneWayPlatformPlugin`].
+//!
 //! Spatial queries are handled separately by the [`SpatialQueryPlugin`].
 //!
 //! You can also find several utility methods for computing contacts in [`contact_query`].
@@ -22,7 +24,9 @@ pub mod broad_phase;
 ))]
 pub mod contact_query;
 pub mod contact_reporting;
+pub mod hooks;
 pub mod narrow_phase;
+pub mod one_way_platform;
 
 pub mod collider;
 pub use collider::*;
@@ -279,6 +283,9 @@ pub struct Contacts {
     /// A list of contact manifolds between two colliders.
     /// Each manifold contains one or more contact points, but each contact
     /// in a given manifold shares the same contact normal.
+    ///
+    /// This is empty for collisions with [sensors](Sensor) that only test for intersections.
+    /// See [`SensorContacts`].
     pub manifolds: Vec<ContactManifold>,
     /// True if either of the colliders involved is a sensor.
     pub is_sensor: bool,
@@ -286,6 +293,11 @@ pub struct Contacts {
     pub during_current_frame: bool,
     /// True if the bodies were in contact during the previous frame.
     pub during_previous_frame: bool,
+    /// The relative motion of the colliders at the deepest contact point when they started touching.
+    ///
+    /// This is only computed by the narrow phase on the frame that the contact starts,
+    /// and is `None` if the colliders were already in contact during the previous frame.
+    pub impact: Option<ContactImpact>,
     /// The total normal impulse applied to the first body in a collision.
     ///
     /// To get the corresponding force, divide the impulse by `Time<Substeps>::delta_seconds()`.
@@ -334,6 +346,26 @@ impl Contacts {
     }
 }
 
+/// The relative motion of two colliders at the moment they started touching,
+/// captured by the narrow phase before the contact is solved.
+///
+/// See [`Contacts::impact`] and [`CollisionImpact`].
+#[derive(Clone, Copy, Debug, PartialEq)]
+#[cfg_attr(feature = "serialize", derive(serde::Serialize, serde::Deserialize))]
+pub struct ContactImpact {
+    /// The world-space contact point with the largest penetration, on the surface of the first collider.
+    pub point: Vector,
+    /// The world-space contact normal at the [`point`](Self::point),
+    /// pointing from the first collider towards the second collider.
+    pub normal: Vector,
+    /// The velocity of the second body relative to the first body at the [`point`](Self::point)
+    /// before the contact was solved.
+    pub relative_velocity: Vector,
+    /// The speed at which the bodies were approaching each other along the [`normal`](Self::normal)
+    /// before the contact was solved. Negative if the bodies were moving apart.
+    pub approach_speed: Scalar,
+}
+
 /// A contact manifold between two colliders, containing a set of contact points.
 /// Each contact in a manifold shares the same contact normal.
 #[derive(Clone, Debug, PartialEq)]
@@ -349,6 +381,38 @@ pub struct ContactManifold {
     pub normal2: Vector,
     /// The index of the manifold in the collision.
     pub index: usize,
+    /// The index of the sub-shape of the first collider that the contacts in this manifold belong to.
+    ///
+    /// For [compound](Collider::compound) colliders, this is the index of the child shape.
+    /// For [trimesh](Collider::trimesh) and 3D [heightfield](Collider::heightfield) colliders,
+    /// it is the index of the triangle, and for [polyline](Collider::polyline) and 2D heightfield colliders,
+    /// it is the index of the segment. `None` for shapes that don't consist of sub-shapes.
+    pub subshape_index1: Option<u32>,
+    /// The index of the sub-shape of the second collider that the contacts in this manifold belong to.
+    ///
+    /// See [`subshape_index1`](Self::subshape_index1) for more details.
+    pub subshape_index2: Option<u32>,
+    /// The effective coefficient of [`Friction`] used for the contacts in this manifold.
+    ///
+    /// This is computed by the narrow phase by combining the friction of the colliders
+    /// or their rigid bodies, or the [`SubShapeMaterials`] of the sub-shapes in contact,
+    /// and it can be changed using [`CollisionHooks`].
+    pub friction: Friction,
+    /// The effective coefficient of [`Restitution`] used for the contacts in this manifold.
+    ///
+    /// This is computed by the narrow phase by combining the restitution of the colliders
+    /// or their rigid bodies, or the [`SubShapeMaterials`] of the sub-shapes in contact,
+    /// and it can be changed using [`CollisionHooks`].
+    pub restitution: Restitution,
+    /// The target relative velocity of the second body with respect to the first body
+    /// along the contact surface, expressed in world space.
+    ///
+    /// Friction drives the relative tangential velocity at the contact points towards
+    /// this velocity instead of zero. The component along the contact normal is ignored.
+    ///
+    /// This is computed by the narrow phase from the [`SurfaceVelocity`] of the colliders,
+    /// and it can be changed using [`CollisionHooks`].
+    pub tangent_velocity: Vector,
 }
 
 impl ContactManifold {
@@ -404,6 +468,178 @@ impl ContactManifold {
             }
         }
     }
+
+    /// Returns the contact with the largest penetration depth, or `None` if there are no contacts.
+    pub fn find_deepest_contact(&self) -> Option<&ContactData> {
+        self.contacts
+            .iter()
+            .max_by(|a, b| a.penetration.total_cmp(&b.penetration))
+    }
+
+    /// Returns `true` if the contact surfaces of `self` and `other` lie on the same plane,
+    /// meaning that the contact normals are nearly equal and the deepest contact points are
+    /// at most `distance_tolerance` apart along the normal.
+    ///
+    /// This can be used to find the manifolds of adjacent triangles of a [trimesh](Collider::trimesh)
+    /// that could be merged into a single manifold.
+    pub fn is_coplanar_with(&self, other: &ContactManifold, distance_tolerance: Scalar) -> bool {
+        // The tolerance for the dot product of the unit normals.
+        const NORMAL_TOLERANCE: Scalar = 1e-3;
+
+        let (Some(contact1), Some(contact2)) =
+            (self.find_deepest_contact(), other.find_deepest_contact())
+        else {
+            return false;
+        };
+
+        self.normal1.dot(other.normal1) >= 1.0 - NORMAL_TOLERANCE
+            && self.normal2.dot(other.normal2) >= 1.0 - NORMAL_TOLERANCE
+            && (contact2.point1 - contact1.point1).dot(self.normal1).abs() <= distance_tolerance
+    }
+
+    /// Reduces the number of contacts in this manifold to at most four in 3D and two in 2D,
+    /// keeping the contacts that cover the largest contact area.
+    ///
+    /// In 3D, the deepest contact is always kept, along with the contact farthest away from it
+    /// and the two contacts that maximize the area of the contact polygon on either side.
+    /// In 2D, the contacts at the two ends of the contact surface are kept.
+    ///
+    /// The order of the remaining contacts is preserved.
+    pub fn reduce_contacts(&mut self) {
+        if self.contacts.len() <= MAX_REDUCED_CONTACTS {
+            return;
+        }
+
+        let indices = contact_area_indices(&self.contacts, self.normal1);
+
+        let mut i = 0;
+        self.contacts.retain(|_| {
+            let keep = indices.contains(&i);
+            i += 1;
+            keep
+        });
+    }
+
+    /// Tries to update the penetration depths of the contacts for new positions and rotations
+    /// of the colliders without recomputing the contacts.
+    ///
+    /// The contacts are only updated if the contact normals of the colliders differ
+    /// by at most `angle_tolerance` radians, and the contact points of the second collider
+    /// have moved at most `distance_tolerance` relative to the contact points of the first collider,
+    /// both along the contact surface and along the contact normal. The colliders must also
+    /// still be within `max_distance` of each other at every contact.
+    ///
+    /// Returns `true` if the contacts were updated, and `false` otherwise.
+    #[allow(clippy::too_many_arguments)]
+    pub fn try_update_contacts(
+        &mut self,
+        position1: Vector,
+        rotation1: &Rotation,
+        position2: Vector,
+        rotation2: &Rotation,
+        distance_tolerance: Scalar,
+        angle_tolerance: Scalar,
+        max_distance: Scalar,
+    ) -> bool {
+        if self.contacts.is_empty() {
+            return false;
+        }
+
+        // Transforms from the local space of the second collider to the local space of the first collider.
+        let inverse_rotation1 = rotation1.inverse();
+        let transform_vector = |vector: Vector| inverse_rotation1 * (*rotation2 * vector);
+        let transform_point =
+            |point: Vector| inverse_rotation1 * (position2 + *rotation2 * point - position1);
+
+        // The normals must still point in opposite directions.
+        if self.normal1.dot(-transform_vector(self.normal2)) < angle_tolerance.cos() {
+            return false;
+        }
+
+        // The contact points must not have slid too far along the contact surface,
+        // moved too far along the normal, or separated beyond the maximum contact distance.
+        let distance_tolerance_squared = distance_tolerance.powi(2);
+        let is_within_tolerance = self.contacts.iter().all(|contact| {
+            let offset = transform_point(contact.point2) - contact.point1;
+            let penetration = -offset.dot(self.normal1);
+            let tangential_offset = offset + self.normal1 * penetration;
+            tangential_offset.length_squared() <= distance_tolerance_squared
+                && (penetration - contact.penetration).abs() <= distance_tolerance
+                && -penetration <= max_distance
+        });
+
+        if !is_within_tolerance {
+            return false;
+        }
+
+        for contact in self.contacts.iter_mut() {
+            let offset = transform_point(contact.point2) - contact.point1;
+            contact.penetration = -offset.dot(self.normal1);
+        }
+
+        true
+    }
+}
+
+/// The maximum number of contacts kept by [`ContactManifold::reduce_contacts`].
+#[cfg(feature = "2d")]
+pub(crate) const MAX_REDUCED_CONTACTS: usize = 2;
+/// The maximum number of contacts kept by [`ContactManifold::reduce_contacts`].
+#[cfg(feature = "3d")]
+pub(crate) const MAX_REDUCED_CONTACTS: usize = 4;
+
+/// Returns the indices of the `contacts` that cover the largest contact area
+/// on the contact plane with the given `normal`. See [`ContactManifold::reduce_contacts`].
+///
+/// The same index can be returned more than once if fewer contacts cover the area.
+pub(crate) fn contact_area_indices(
+    contacts: &[ContactData],
+    normal: Vector,
+) -> [usize; MAX_REDUCED_CONTACTS] {
+    let max_index_by = |f: &dyn Fn(Vector) -> Scalar| {
+        contacts
+            .iter()
+            .map(|contact| contact.point1)
+            .enumerate()
+            .max_by(|(_, a), (_, b)| f(*a).total_cmp(&f(*b)))
+            .map_or(0, |(i, _)| i)
+    };
+
+    #[cfg(feature = "2d")]
+    {
+        // Keep the points at the ends of the contact surface along the tangent.
+        let tangent = normal.perp();
+        [
+            max_index_by(&|point| -point.dot(tangent)),
+            max_index_by(&|point| point.dot(tangent)),
+        ]
+    }
+
+    #[cfg(feature = "3d")]
+    {
+        // Start with the deepest contact.
+        let deepest_index = contacts
+            .iter()
+            .enumerate()
+            .max_by(|(_, a), (_, b)| a.penetration.total_cmp(&b.penetration))
+            .map_or(0, |(i, _)| i);
+        let point0 = contacts[deepest_index].point1;
+
+        // Find the point farthest away from the deepest point.
+        let farthest_index = max_index_by(&|point| point.distance_squared(point0));
+        let point1 = contacts[farthest_index].point1;
+
+        // Find the points that maximize the signed area of the triangle
+        // formed with the first two points on either side of the edge between them.
+        let edge = point1 - point0;
+        let signed_area = |point: Vector| edge.cross(point - point0).dot(normal);
+        [
+            deepest_index,
+            farthest_index,
+            max_index_by(&signed_area),
+            max_index_by(&|point| -signed_area(point)),
+        ]
+    }
 }
 
 /// Data related to a single contact between two bodies.
@@ -502,6 +738,12 @@ pub struct ContactData {
     /// The contact feature ID on the first shape. This indicates the ID of
     /// the vertex, edge, or face of the contact, if one can be determined.
     pub feature_id2: PackedFeatureId,
+    /// Overrides the [`friction`](ContactManifold::friction) of the manifold for this contact.
+    pub friction: Option<Friction>,
+    /// Overrides the [`restitution`](ContactManifold::restitution) of the manifold for this contact.
+    pub restitution: Option<Restitution>,
+    /// Overrides the [`tangent_velocity`](ContactManifold::tangent_velocity) of the manifold for this contact.
+    pub tangent_velocity: Option<Vector>,
 }
 
 impl ContactData {
@@ -526,6 +768,9 @@ impl ContactData {
             tangent_impulse: default(),
             feature_id1: PackedFeatureId::UNKNOWN,
             feature_id2: PackedFeatureId::UNKNOWN,
+            friction: None,
+            restitution: None,
+            tangent_velocity: None,
         }
     }
 
@@ -5,6 +5,7 @@
 use std::marker::PhantomData;
 
 use crate::{
+    collision::{contact_area_indices, MAX_REDUCED_CONTACTS},
     dynamics::solver::{
         contact::ContactConstraint, ContactConstraints, ContactSoftnessCoefficients,
     },
@@ -16,7 +17,7 @@ use bevy::{
     ecs::{
         intern::Interned,
         schedule::{ExecutorKind, LogLevel, ScheduleBuildSettings, ScheduleLabel},
-        system::SystemParam,
+        system::{StaticSystemParam, SystemParam, SystemParamItem},
     },
     prelude::*,
 };
@@ -33,17 +34,21 @@ use bevy::{
 /// The plugin takes a collider type. This should be [`Collider`] for
 /// the vast majority of applications, but for custom collisión backends
 /// you may use any collider that implements the [`AnyCollider`] trait.
-pub struct NarrowPhasePlugin<C: AnyCollider> {
+///
+/// The plugin can also take a type implementing [`CollisionHooks`] for filtering collision pairs
+/// and modifying the contacts and their material properties before contact constraints are generated.
+/// By default, no hooks are used.
+pub struct NarrowPhasePlugin<C: AnyCollider, H: CollisionHooks = ()> {
     schedule: Interned<dyn ScheduleLabel>,
     /// If `true`, the narrow phase will generate [`ContactConstraint`]s
     /// and add them to the [`ContactConstraints`] resource.
     ///
     /// Contact constraints are used by the [`SolverPlugin`] for solving contacts.
     generate_constraints: bool,
-    _phantom: PhantomData<C>,
+    _phantom: PhantomData<(C, H)>,
 }
 
-impl<C: AnyCollider> NarrowPhasePlugin<C> {
+impl<C: AnyCollider, H: CollisionHooks> NarrowPhasePlugin<C, H> {
     /// Creates a [`NarrowPhasePlugin`] with the schedule used for running its systems
     /// and whether it should generate [`ContactConstraint`]s for the [`ContactConstraints`] resource.
     ///
@@ -59,13 +64,16 @@ impl<C: AnyCollider> NarrowPhasePlugin<C> {
     }
 }
 
-impl<C: AnyCollider> Default for NarrowPhasePlugin<C> {
+impl<C: AnyCollider, H: CollisionHooks> Default for NarrowPhasePlugin<C, H> {
     fn default() -> Self {
         Self::new(PhysicsSchedule, true)
     }
 }
 
-impl<C: AnyCollider> Plugin for NarrowPhasePlugin<C> {
+impl<C: AnyCollider, H: CollisionHooks + 'static> Plugin for NarrowPhasePlugin<C, H>
+where
+    for<'w, 's> SystemParamItem<'w, 's, H>: CollisionHooks,
+{
     fn build(&self, app: &mut App) {
         // For some systems, we only want one instance, even if there are multiple
         // NarrowPhasePlugin instances with different collider types.
@@ -127,7 +135,7 @@ impl<C: AnyCollider> Plugin for NarrowPhasePlugin<C> {
         // Collect contacts into `Collisions`.
         app.add_systems(
             self.schedule,
-            collect_collisions::<C>
+            collect_collisions::<C, H>
                 .in_set(NarrowPhaseSet::CollectCollisions)
                 // Allowing ambiguities is required so that it's possible
                 // to have multiple collision backends at the same time.
@@ -212,6 +220,37 @@ pub struct NarrowPhaseConfig {
     ///
     /// Default: `true`
     pub match_contacts: bool,
+
+    /// If `true`, contact manifolds are reduced after they are computed to lower the number
+    /// of contacts that the solver needs to handle.
+    ///
+    /// Manifolds with the same contact plane, such as the manifolds of adjacent triangles
+    /// of a [trimesh](Collider::trimesh) or [heightfield](Collider::heightfield), are merged
+    /// into a single manifold. Then, the contacts of the collision pair are reduced to the four contacts
+    /// (or two in 2D) that cover the largest contact area. See [`ContactManifold::reduce_contacts`].
+    ///
+    /// This can improve performance and make stacking more stable for bodies resting on dense geometry.
+    ///
+    /// The limit applies to all manifolds of the pair together, and the contact area is measured
+    /// on the contact plane of the deepest contact. Manifolds with different contact planes,
+    /// such as the manifolds of a body resting in a corner, are not merged, and manifolds whose contacts
+    /// don't add to the contact area are removed. Manifolds of sub-shapes with different [`SubShapeMaterials`]
+    /// are not merged either, so that each keeps its own material. The sub-shape indices of a merged manifold
+    /// are the ones of the first manifold.
+    ///
+    /// Default: `false`
+    pub reduce_manifolds: bool,
+
+    /// If set, the contact manifolds from the previous frame are reused for a collision
+    /// if the relative position and rotation of the colliders have barely changed,
+    /// skipping the computation of new contact manifolds. The contact impulses
+    /// and penetration depths are kept up to date for the reused contacts.
+    ///
+    /// This can improve performance for resting bodies, such as large stacks,
+    /// at the cost of slightly less accurate contacts.
+    ///
+    /// Default: `None`
+    pub contact_cache: Option<ContactCacheTolerance>,
 }
 
 impl Default for NarrowPhaseConfig {
@@ -220,6 +259,39 @@ impl Default for NarrowPhaseConfig {
             default_speculative_margin: Scalar::MAX,
             contact_tolerance: 0.005,
             match_contacts: true,
+            reduce_manifolds: false,
+            contact_cache: None,
+        }
+    }
+}
+
+/// Tolerances for reusing the contact manifolds of a collision from the previous frame.
+/// See [`NarrowPhaseConfig::contact_cache`].
+#[derive(Reflect, Clone, Copy, Debug, PartialEq)]
+#[cfg_attr(feature = "serialize", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serialize", reflect(Serialize, Deserialize))]
+#[reflect(Debug, PartialEq)]
+pub struct ContactCacheTolerance {
+    /// The maximum distance that the contact points of the colliders can move relative to each other,
+    /// along the contact surface or along the contact normal, for the contacts to be reused.
+    ///
+    /// This is implicitly scaled by the [`PhysicsLengthUnit`].
+    ///
+    /// Default: `0.005`
+    pub distance: Scalar,
+
+    /// The maximum angle in radians that the contact normals of the colliders
+    /// can rotate relative to each other for the contacts to be reused.
+    ///
+    /// Default: `0.01`
+    pub angle: Scalar,
+}
+
+impl Default for ContactCacheTolerance {
+    fn default() -> Self {
+        Self {
+            distance: 0.005,
+            angle: 0.01,
         }
     }
 }
@@ -243,12 +315,19 @@ pub enum NarrowPhaseSet {
     Last,
 }
 
-fn collect_collisions<C: AnyCollider>(
+fn collect_collisions<C: AnyCollider, H: CollisionHooks>(
     mut narrow_phase: NarrowPhase<C>,
     broad_collision_pairs: Res<BroadCollisionPairs>,
+    hooks: StaticSystemParam<H>,
     time: Res<Time>,
-) {
-    narrow_phase.update(&broad_collision_pairs, time.delta_seconds_adjusted());
+) where
+    for<'w, 's> SystemParamItem<'w, 's, H>: CollisionHooks,
+{
+    narrow_phase.update(
+        &broad_collision_pairs,
+        &*hooks,
+        time.delta_seconds_adjusted(),
+    );
 }
 
 // TODO: It'd be nice to generate the constraint in the same parallel loop as `collect_collisions`
@@ -340,6 +419,7 @@ pub struct NarrowPhase<'w, 's, C: AnyCollider> {
             Option<&'static SpeculativeMargin>,
         ),
     >,
+    joint_collision_exclusions: Option<Res<'w, JointCollisionExclusions>>,
     /// Contacts found by the narrow phase.
     pub collisions: ResMut<'w, Collisions>,
     /// Configuration options for the narrow phase.
@@ -352,8 +432,14 @@ pub struct NarrowPhase<'w, 's, C: AnyCollider> {
 
 impl<'w, 's, C: AnyCollider> NarrowPhase<'w, 's, C> {
     /// Updates the narrow phase by computing [`Contacts`] based on [`BroadCollisionPairs`]
-    /// and adding them to [`Collisions`].
-    fn update(&mut self, broad_collision_pairs: &[(Entity, Entity)], delta_secs: Scalar) {
+    /// and adding them to [`Collisions`]. The pairs and contacts are filtered and modified
+    /// using the given [`CollisionHooks`].
+    fn update<H: CollisionHooks>(
+        &mut self,
+        broad_collision_pairs: &[(Entity, Entity)],
+        hooks: &H,
+        delta_secs: Scalar,
+    ) {
         // TODO: These scaled versions could be in their own resource
         //       and updated just before physics every frame.
         // Cache default margins scaled by the length unit.
@@ -370,23 +456,34 @@ impl<'w, 's, C: AnyCollider> NarrowPhase<'w, 's, C> {
                 .iter()
                 .par_splat_map(ComputeTaskPool::get(), None, |_i, chunks| {
                     let mut new_collisions = Vec::<Contacts>::with_capacity(chunks.len());
+                    let mut ended_collisions = Vec::<(Entity, Entity)>::new();
 
                     // Compute contacts for this intersection pair and generate
                     // contact constraints for them.
                     for &(entity1, entity2) in chunks {
+                        if self.is_pair_inactive(entity1, entity2) {
+                            continue;
+                        }
+
                         if let Some(contacts) =
-                            self.handle_entity_pair(entity1, entity2, delta_secs)
+                            self.handle_entity_pair_with_hooks(entity1, entity2, hooks, delta_secs)
                         {
                             new_collisions.push(contacts);
+                        } else if self.is_collision_ended(entity1, entity2) {
+                            ended_collisions.push((entity1, entity2));
                         }
                     }
 
-                    new_collisions
+                    (new_collisions, ended_collisions)
                 })
                 .into_iter()
-                .for_each(|new_collisions| {
+                .for_each(|(new_collisions, ended_collisions)| {
                     // Add the collisions and constraints from each chunk.
                     self.collisions.extend(new_collisions);
+
+                    for (entity1, entity2) in ended_collisions {
+                        self.end_collision(entity1, entity2);
+                    }
                 });
         }
         #[cfg(not(feature = "parallel"))]
@@ -394,13 +491,85 @@ impl<'w, 's, C: AnyCollider> NarrowPhase<'w, 's, C> {
             // Compute contacts for this intersection pair and generate
             // contact constraints for them.
             for &(entity1, entity2) in broad_collision_pairs {
-                if let Some(contacts) = self.handle_entity_pair(entity1, entity2, delta_secs) {
+                if self.is_pair_inactive(entity1, entity2) {
+                    continue;
+                }
+
+                if let Some(contacts) =
+                    self.handle_entity_pair_with_hooks(entity1, entity2, hooks, delta_secs)
+                {
                     self.collisions.insert_collision_pair(contacts);
+                } else if self.is_collision_ended(entity1, entity2) {
+                    self.end_collision(entity1, entity2);
                 }
             }
         }
     }
 
+    /// Returns `true` if the colliders are both attached to static or sleeping rigid bodies.
+    ///
+    /// Their contacts can't change, so the pair is skipped, and the previous contacts are kept.
+    fn is_pair_inactive(&self, entity1: Entity, entity2: Entity) -> bool {
+        let is_inactive = |entity: Entity| {
+            self.collider_query
+                .get(entity)
+                .ok()
+                .and_then(|collider| collider.parent)
+                .and_then(|parent| self.body_query.get(parent.get()).ok())
+                .is_some_and(|(body, _, _)| body.rb.is_static() || body.is_sleeping)
+        };
+        is_inactive(entity1) && is_inactive(entity2)
+    }
+
+    /// Returns `true` if the colliders of this narrow phase were colliding,
+    /// but no contacts were found for them anymore.
+    ///
+    /// Pairs of colliders handled by other narrow phases are ignored.
+    fn is_collision_ended(&self, entity1: Entity, entity2: Entity) -> bool {
+        self.collisions.contains(entity1, entity2)
+            && self.collider_query.contains(entity1)
+            && self.collider_query.contains(entity2)
+    }
+
+    /// Marks the collision between `entity1` and `entity2` as no longer colliding,
+    /// so that it ends after contact reporting.
+    fn end_collision(&mut self, entity1: Entity, entity2: Entity) {
+        if let Some(contacts) = self.collisions.get_mut(entity1, entity2) {
+            contacts.total_normal_impulse = 0.0;
+            contacts.total_tangent_impulse = default();
+            contacts.during_current_frame = false;
+        }
+    }
+
+    /// Computes the [`Contacts`] between `entity1` and `entity2` using [`handle_entity_pair`](Self::handle_entity_pair),
+    /// and filters and modifies them using the given [`CollisionHooks`].
+    ///
+    /// Returns `None` if there are no contacts or if the hooks rejected the pair or the contacts.
+    fn handle_entity_pair_with_hooks<H: CollisionHooks>(
+        &self,
+        entity1: Entity,
+        entity2: Entity,
+        hooks: &H,
+        delta_secs: Scalar,
+    ) -> Option<Contacts> {
+        // Filter the pair before computing any contacts for it.
+        let keep_pair = self
+            .parallel_commands
+            .command_scope(|mut commands| hooks.filter_pairs(entity1, entity2, &mut commands));
+
+        if !keep_pair {
+            return None;
+        }
+
+        let mut contacts = self.handle_entity_pair(entity1, entity2, delta_secs)?;
+
+        let keep_contacts = self
+            .parallel_commands
+            .command_scope(|mut commands| hooks.modify_contacts(&mut contacts, &mut commands));
+
+        keep_contacts.then_some(contacts)
+    }
+
     /// Returns the [`Contacts`] between `entity1` and `entity2` if they are intersecting
     /// or expected to start intersecting within the next frame. This includes
     /// [speculative collision](dynamics::ccd#speculative-collision).
@@ -415,6 +584,17 @@ impl<'w, 's, C: AnyCollider> NarrowPhase<'w, 's, C> {
             return None;
         };
 
+        // Skip bodies connected by joints that have collisions disabled.
+        if let (Some(parent1), Some(parent2), Some(exclusions)) = (
+            collider1.parent,
+            collider2.parent,
+            &self.joint_collision_exclusions,
+        ) {
+            if exclusions.contains(parent1.get(), parent2.get()) {
+                return None;
+            }
+        }
+
         let body1_bundle = collider1
             .parent
             .and_then(|p| self.body_query.get(p.get()).ok());
@@ -505,7 +685,95 @@ impl<'w, 's, C: AnyCollider> NarrowPhase<'w, 's, C> {
         let max_contact_distance =
             effective_speculative_margin.max(*self.contact_tolerance) + collision_margin_sum;
 
-        self.compute_contact_pair(&collider1, &collider2, max_contact_distance)
+        let mut contacts =
+            self.compute_contact_pair(&collider1, &collider2, max_contact_distance)?;
+
+        // Get the friction and restitution coefficients of the colliders
+        // or the bodies they are attached to.
+        let friction1 = collider1
+            .friction
+            .or(body1_bundle.as_ref().map(|(body, _, _)| body.friction))
+            .copied()
+            .unwrap_or_default();
+        let friction2 = collider2
+            .friction
+            .or(body2_bundle.as_ref().map(|(body, _, _)| body.friction))
+            .copied()
+            .unwrap_or_default();
+        let restitution1 = collider1
+            .restitution
+            .or(body1_bundle.as_ref().map(|(body, _, _)| body.restitution))
+            .copied()
+            .unwrap_or_default();
+        let restitution2 = collider2
+            .restitution
+            .or(body2_bundle.as_ref().map(|(body, _, _)| body.restitution))
+            .copied()
+            .unwrap_or_default();
+
+        // The contact points on each surface move with the surface velocity of the collider,
+        // so friction drives the relative velocity of the bodies towards the difference
+        // of the surface velocities.
+        let surface_velocity1 = collider1
+            .surface_velocity
+            .map_or(Vector::ZERO, |velocity| *collider1.rotation * velocity.0);
+        let surface_velocity2 = collider2
+            .surface_velocity
+            .map_or(Vector::ZERO, |velocity| *collider2.rotation * velocity.0);
+        let tangent_velocity = surface_velocity1 - surface_velocity2;
+
+        for manifold in contacts.manifolds.iter_mut() {
+            // Use the materials of the sub-shapes in contact if they have one.
+            let material1 = collider1.subshape_material(manifold.subshape_index1);
+            let material2 = collider2.subshape_material(manifold.subshape_index2);
+
+            manifold.friction = material1
+                .map_or(friction1, |material| material.friction)
+                .combine(material2.map_or(friction2, |material| material.friction));
+            manifold.restitution = material1
+                .map_or(restitution1, |material| material.restitution)
+                .combine(material2.map_or(restitution2, |material| material.restitution));
+            manifold.tangent_velocity = tangent_velocity;
+        }
+
+        // Capture the relative motion at the deepest contact point when the contact starts,
+        // before the solver has changed the velocities of the bodies.
+        if !contacts.during_previous_frame {
+            let deepest_contact = contacts
+                .manifolds
+                .iter()
+                .flat_map(|manifold| {
+                    manifold
+                        .contacts
+                        .iter()
+                        .map(move |contact| (manifold, contact))
+                })
+                .max_by(|(_, a), (_, b)| a.penetration.total_cmp(&b.penetration));
+
+            if let Some((manifold, contact)) = deepest_contact {
+                let point = collider1.current_position() + *collider1.rotation * contact.point1;
+                let normal = manifold.global_normal1(&collider1.rotation);
+
+                let velocity_at_point = |body: Option<&RigidBodyQueryReadOnlyItem>| {
+                    body.map_or(Vector::ZERO, |body| {
+                        let center_of_mass =
+                            body.current_position() + *body.rotation * body.center_of_mass.0;
+                        body.velocity_at_point(point - center_of_mass)
+                    })
+                };
+                let relative_velocity = velocity_at_point(body2_bundle.as_ref().map(|b| &b.0))
+                    - velocity_at_point(body1_bundle.as_ref().map(|b| &b.0));
+
+                contacts.impact = Some(ContactImpact {
+                    point,
+                    normal,
+                    relative_velocity,
+                    approach_speed: -normal.dot(relative_velocity),
+                });
+            }
+        }
+
+        Some(contacts)
     }
 
     /// Computes contacts between `collider1` and `collider2`.
@@ -524,17 +792,11 @@ impl<'w, 's, C: AnyCollider> NarrowPhase<'w, 's, C> {
         let position1 = collider1.current_position();
         let position2 = collider2.current_position();
 
-        // TODO: It'd be good to persist the manifolds and let Parry match contacts.
-        //       This isn't currently done because it requires using Parry's contact manifold type.
-        // Compute the contact manifolds using the effective speculative margin.
-        let mut manifolds = collider1.shape.contact_manifolds(
-            collider2.shape,
-            position1,
-            *collider1.rotation,
-            position2,
-            *collider2.rotation,
-            max_distance,
-        );
+        // Sensors only need to know if they are intersecting other colliders,
+        // unless they need contact points and have opted in with `SensorContacts`.
+        let intersection_only = (collider1.is_sensor || collider2.is_sensor)
+            && !(collider1.is_sensor && collider1.has_sensor_contacts)
+            && !(collider2.is_sensor && collider2.has_sensor_contacts);
 
         // Get the previous contacts if there are any.
         let previous_contacts = self
@@ -546,13 +808,75 @@ impl<'w, 's, C: AnyCollider> NarrowPhase<'w, 's, C> {
                 .get_internal()
                 .get(&(collider2.entity, collider1.entity)));
 
+        let mut is_reused = false;
+
+        let mut manifolds = if intersection_only {
+            let intersecting = collider1.shape.intersection_test(
+                collider2.shape,
+                position1,
+                *collider1.rotation,
+                position2,
+                *collider2.rotation,
+            );
+
+            if !intersecting {
+                return None;
+            }
+
+            vec![]
+        } else if let Some(manifolds) =
+            self.reuse_previous_manifolds(previous_contacts, collider1, collider2, max_distance)
+        {
+            is_reused = true;
+            manifolds
+        } else {
+            // TODO: It'd be good to persist the manifolds and let Parry match contacts.
+            //       This isn't currently done because it requires using Parry's contact manifold type.
+            // Compute the contact manifolds using the effective speculative margin.
+            let mut manifolds = collider1.shape.contact_manifolds(
+                collider2.shape,
+                position1,
+                *collider1.rotation,
+                position2,
+                *collider2.rotation,
+                max_distance,
+            );
+
+            // Merge coplanar manifolds and reduce the number of contacts in each manifold.
+            // Only manifolds whose sub-shapes have the same materials are merged,
+            // so that the materials of the sub-shapes are preserved.
+            if self.config.reduce_manifolds {
+                reduce_manifolds(&mut manifolds, *self.contact_tolerance, |a, b| {
+                    collider1.subshape_material(a.subshape_index1)
+                        == collider1.subshape_material(b.subshape_index1)
+                        && collider2.subshape_material(a.subshape_index2)
+                            == collider2.subshape_material(b.subshape_index2)
+                });
+            }
+
+            if manifolds.is_empty() {
+                return None;
+            }
+
+            manifolds
+        };
+
         let mut total_normal_impulse = 0.0;
         let mut total_tangent_impulse = default();
 
         // Match contacts and copy previous contact impulses for warm starting the solver.
         // TODO: This condition is pretty arbitrary, mainly to skip dense trimeshes.
         //       If we let Parry handle contact matching, this wouldn't be needed.
-        if manifolds.len() <= 4 && self.config.match_contacts {
+        if is_reused {
+            // Reused contacts already have the impulses from the previous frame.
+            for contact in manifolds
+                .iter()
+                .flat_map(|manifold| manifold.contacts.iter())
+            {
+                total_normal_impulse += contact.normal_impulse;
+                total_tangent_impulse += contact.tangent_impulse;
+            }
+        } else if manifolds.len() <= 4 && self.config.match_contacts {
             if let Some(previous_contacts) = previous_contacts {
                 // TODO: Cache this?
                 let distance_threshold = 0.1 * self.length_unit.0;
@@ -579,6 +903,7 @@ impl<'w, 's, C: AnyCollider> NarrowPhase<'w, 's, C> {
             during_current_frame: true,
             during_previous_frame: previous_contacts.map_or(false, |c| c.during_previous_frame),
             manifolds,
+            impact: None,
             is_sensor: collider1.is_sensor
                 || collider2.is_sensor
                 || !collider1.is_rb
@@ -587,7 +912,59 @@ impl<'w, 's, C: AnyCollider> NarrowPhase<'w, 's, C> {
             total_tangent_impulse,
         };
 
-        (!contacts.manifolds.is_empty()).then_some(contacts)
+        Some(contacts)
+    }
+
+    /// Returns the manifolds of the `previous_contacts` updated for the current positions and rotations
+    /// of the colliders, or `None` if the [contact cache](NarrowPhaseConfig::contact_cache) is disabled
+    /// or the colliders have moved too much relative to each other for the manifolds to be reused.
+    fn reuse_previous_manifolds(
+        &self,
+        previous_contacts: Option<&Contacts>,
+        collider1: &ColliderQueryItem<C>,
+        collider2: &ColliderQueryItem<C>,
+        max_distance: Scalar,
+    ) -> Option<Vec<ContactManifold>> {
+        let tolerance = self.config.contact_cache?;
+        let previous_contacts = previous_contacts.filter(|contacts| {
+            contacts.entity1 == collider1.entity
+                && contacts.during_previous_frame
+                && !contacts.manifolds.is_empty()
+        })?;
+
+        let position1 = collider1.current_position();
+        let position2 = collider2.current_position();
+        let distance_tolerance = tolerance.distance * self.length_unit.0;
+
+        let mut manifolds = previous_contacts.manifolds.clone();
+        let is_updated = manifolds.iter_mut().all(|manifold| {
+            manifold.try_update_contacts(
+                position1,
+                &collider1.rotation,
+                position2,
+                &collider2.rotation,
+                distance_tolerance,
+                tolerance.angle,
+                max_distance,
+            )
+        });
+
+        if !is_updated {
+            return None;
+        }
+
+        // The previous manifolds were already modified by the collision hooks,
+        // so the per-contact overrides must be reset before the hooks run again.
+        for contact in manifolds
+            .iter_mut()
+            .flat_map(|manifold| &mut manifold.contacts)
+        {
+            contact.friction = None;
+            contact.restitution = None;
+            contact.tangent_velocity = None;
+        }
+
+        Some(manifolds)
     }
 
     /// Generates [`ContactConstraint`]s for the given bodies and their corresponding colliders
@@ -632,17 +1009,6 @@ impl<'w, 's, C: AnyCollider> NarrowPhase<'w, 's, C> {
             }
         });
 
-        // Get combined friction and restitution coefficients of the colliders
-        // or the bodies they are attached to.
-        let friction = collider1
-            .friction
-            .unwrap_or(body1.friction)
-            .combine(*collider2.friction.unwrap_or(body2.friction));
-        let restitution = collider1
-            .restitution
-            .unwrap_or(body1.restitution)
-            .combine(*collider2.restitution.unwrap_or(body2.restitution));
-
         let contact_softness = if !body1.rb.is_dynamic() || !body2.rb.is_dynamic() {
             contact_softness.non_dynamic
         } else {
@@ -663,8 +1029,6 @@ impl<'w, 's, C: AnyCollider> NarrowPhase<'w, 's, C> {
                 collision_margin,
                 // TODO: Shouldn't this be the effective speculative margin?
                 *self.default_speculative_margin,
-                friction,
-                restitution,
                 contact_softness,
                 self.config.match_contacts,
                 delta_secs,
@@ -678,7 +1042,7 @@ impl<'w, 's, C: AnyCollider> NarrowPhase<'w, 's, C> {
 }
 
 #[cfg(debug_assertions)]
-fn log_overlap_at_spawn(
+pub(crate) fn log_overlap_at_spawn(
     collisions: Res<Collisions>,
     added_bodies: Query<(Ref<RigidBody>, Option<&Name>, &Position)>,
 ) {
@@ -710,15 +1074,55 @@ fn log_overlap_at_spawn(
 }
 
 fn remove_ended_collisions(mut collisions: ResMut<Collisions>) {
-    collisions.retain(|contacts| contacts.during_current_frame);
+    collisions.retain(|contacts| {
+        // The remaining collisions were ongoing during this frame.
+        contacts.during_previous_frame = true;
+        contacts.during_current_frame
+    });
 }
 
 // TODO: The collision state handling feels a bit confusing and error-prone.
 //       Ideally, the narrow phase wouldn't need to handle it at all, or it would at least be simpler.
 /// Resets collision states like `during_current_frame` and `during_previous_frame`.
+///
+/// Only the collisions of pairs that stopped overlapping according to [`BroadCollisionPairChanges`] are reset here.
+/// The collisions of pairs that are still overlapping are updated by the narrow phase, which also ends them
+/// if no contacts are found anymore. Pairs of colliders that are both static or sleeping are skipped
+/// by the narrow phase, so they are still in contact.
+///
+/// If the broad phase doesn't track [`BroadCollisionPairChanges`], all collisions are reset.
 pub fn reset_collision_states(
     mut collisions: ResMut<Collisions>,
+    pair_changes: Option<Res<BroadCollisionPairChanges>>,
     query: Query<(Option<&RigidBody>, Has<Sleeping>)>,
+) {
+    let Some(pair_changes) = pair_changes else {
+        reset_all_collision_states(&mut collisions, &query);
+        return;
+    };
+
+    let collisions = collisions.get_internal_mut();
+
+    for (entity1, entity2) in pair_changes.ended() {
+        let contacts = match collisions.get_mut(&(*entity1, *entity2)) {
+            Some(contacts) => contacts,
+            None => match collisions.get_mut(&(*entity2, *entity1)) {
+                Some(contacts) => contacts,
+                None => continue,
+            },
+        };
+
+        contacts.total_normal_impulse = 0.0;
+        contacts.total_tangent_impulse = default();
+        contacts.during_previous_frame = true;
+        contacts.during_current_frame = false;
+    }
+}
+
+/// Resets the collision states of all collisions, checking whether the bodies are active.
+fn reset_all_collision_states(
+    collisions: &mut Collisions,
+    query: &Query<(Option<&RigidBody>, Has<Sleeping>)>,
 ) {
     for contacts in collisions.get_internal_mut().values_mut() {
         contacts.total_normal_impulse = 0.0;
@@ -751,3 +1155,93 @@ fn run_post_process_collisions_schedule(world: &mut World) {
     trace!("running PostProcessCollisions");
     world.run_schedule(PostProcessCollisions);
 }
+
+/// Merges coplanar manifolds and reduces the number of contacts in each manifold
+/// and in the whole contact pair.
+///
+/// Manifolds that are within `distance_tolerance` of each other's contact plane are merged
+/// if `can_merge` returns `true` for them.
+fn reduce_manifolds(
+    manifolds: &mut Vec<ContactManifold>,
+    distance_tolerance: Scalar,
+    can_merge: impl Fn(&ContactManifold, &ContactManifold) -> bool,
+) {
+    let mut i = 0;
+
+    while i < manifolds.len() {
+        let mut j = i + 1;
+        let mut merged = false;
+
+        while j < manifolds.len() {
+            if manifolds[i].is_coplanar_with(&manifolds[j], distance_tolerance)
+                && can_merge(&manifolds[i], &manifolds[j])
+            {
+                let other = manifolds.remove(j);
+                manifolds[i].contacts.extend(other.contacts);
+                merged = true;
+            } else {
+                j += 1;
+            }
+        }
+
+        let manifold = &mut manifolds[i];
+
+        // The feature IDs of different sub-shapes can be equal,
+        // so merged contacts must be matched based on their positions.
+        if merged {
+            for contact in manifold.contacts.iter_mut() {
+                contact.feature_id1 = PackedFeatureId::UNKNOWN;
+                contact.feature_id2 = PackedFeatureId::UNKNOWN;
+            }
+        }
+
+        manifold.reduce_contacts();
+        i += 1;
+    }
+
+    reduce_pair_contacts(manifolds);
+
+    for (i, manifold) in manifolds.iter_mut().enumerate() {
+        manifold.index = i;
+    }
+}
+
+/// Reduces the contacts of all `manifolds` of a contact pair to the ones that cover
+/// the largest contact area, like [`ContactManifold::reduce_contacts`] does for a single manifold.
+///
+/// The contact area is measured on the contact plane of the deepest contact.
+/// Manifolds without any remaining contacts are removed.
+fn reduce_pair_contacts(manifolds: &mut Vec<ContactManifold>) {
+    let contacts: Vec<ContactData> = manifolds
+        .iter()
+        .flat_map(|manifold| manifold.contacts.iter().copied())
+        .collect();
+
+    if contacts.len() <= MAX_REDUCED_CONTACTS {
+        return;
+    }
+
+    let Some(deepest_manifold) = manifolds.iter().max_by(|a, b| {
+        let depth = |manifold: &ContactManifold| {
+            manifold
+                .find_deepest_contact()
+                .map_or(Scalar::MIN, |contact| contact.penetration)
+        };
+        depth(a).total_cmp(&depth(b))
+    }) else {
+        return;
+    };
+
+    let indices = contact_area_indices(&contacts, deepest_manifold.normal1);
+
+    let mut i = 0;
+    for manifold in manifolds.iter_mut() {
+        manifold.contacts.retain(|_| {
+            let keep = indices.contains(&i);
+            i += 1;
+            keep
+        });
+    }
+
+    manifolds.retain(|manifold| !manifold.contacts.is_empty());
+}
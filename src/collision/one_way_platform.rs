@@ -0,0 +1,494 @@
+//! One-way platforms that bodies can pass through from one side, but collide with from the other side.
+//!
+//! See [`OneWayPlatformPlugin`].
+
+use super::broad_phase::BroadCollisionPairChanges;
+use super::narrow_phase::NarrowPhaseSet;
+use crate::prelude::*;
+use bevy::{
+    ecs::{
+        entity::{EntityMapper, MapEntities},
+        system::SystemParam,
+    },
+    prelude::*,
+    utils::HashSet,
+};
+
+/// Filters collisions for [`OneWayPlatform`]s, allowing bodies to pass through them
+/// from below while colliding with them from above.
+///
+/// Bodies can control how they interact with one-way platforms using the
+/// [`PassThroughOneWayPlatform`] component. It can also be used to request
+/// a body to [drop through](PassThroughOneWayPlatform::DropThrough) a platform it is standing on.
+///
+/// ```no_run
+#[cfg_attr(feature = "2d", doc = "use avian2d::prelude::*;")]
+#[cfg_attr(feature = "3d", doc = "use avian3d::prelude::*;")]
+/// use bevy::prelude::*;
+///
+/// fn main() {
+///     App::new()
+///         .add_plugins((DefaultPlugins, PhysicsPlugins::default()))
+///         .add_systems(Startup, setup)
+///         .add_systems(Update, drop_through_platforms)
+///         .run();
+/// }
+///
+/// fn setup(mut commands: Commands) {
+///     // A platform that can be jumped through from below.
+///     commands.spawn((
+///         RigidBody::Static,
+#[cfg_attr(feature = "2d", doc = "        Collider::rectangle(10.0, 0.5),")]
+#[cfg_attr(feature = "3d", doc = "        Collider::cuboid(10.0, 0.5, 10.0),")]
+///         OneWayPlatform::default(),
+///     ));
+///
+///     // A player that can drop through platforms.
+///     commands.spawn((
+///         RigidBody::Dynamic,
+///         Collider::capsule(0.5, 1.0),
+///         Transform::from_xyz(0.0, 2.0, 0.0),
+///         PassThroughOneWayPlatform::default(),
+///     ));
+/// }
+///
+/// fn drop_through_platforms(
+///     keyboard_input: Res<ButtonInput<KeyCode>>,
+///     mut query: Query<&mut PassThroughOneWayPlatform>,
+/// ) {
+///     if keyboard_input.just_pressed(KeyCode::ArrowDown) {
+///         for mut pass_through in &mut query {
+///             *pass_through = PassThroughOneWayPlatform::DropThrough;
+///         }
+///     }
+/// }
+/// ```
+///
+/// The contacts are filtered right after the narrow phase has collected them into [`Collisions`],
+/// before [`PostProcessCollisions`] is run. Contacts that bodies are passing through are removed,
+/// and no contact constraints are generated for them. The filtering doesn't use [`CollisionHooks`],
+/// so custom hooks can be used without affecting one-way platforms.
+pub struct OneWayPlatformPlugin;
+
+impl Plugin for OneWayPlatformPlugin {
+    fn build(&self, app: &mut App) {
+        app.register_type::<OneWayPlatform>()
+            .register_type::<PassThroughOneWayPlatform>();
+
+        let physics_schedule = app
+            .get_schedule_mut(PhysicsSchedule)
+            .expect("add PhysicsSchedule first");
+
+        physics_schedule.add_systems((
+            wake_up_passing_bodies.in_set(PhysicsStepSet::First),
+            prune_passing_entities.in_set(NarrowPhaseSet::First),
+            filter_one_way_platform_contacts
+                .after(NarrowPhaseSet::CollectCollisions)
+                .before(NarrowPhaseSet::PostProcess)
+                .in_set(PhysicsStepSet::NarrowPhase),
+        ));
+    }
+}
+
+/// A component for colliders that other colliders can pass through from one side,
+/// but collide with from the other side. Commonly used for platforms in platformers.
+///
+/// A collision is only accepted if the contact normal pointing out of the platform
+/// is within the [`max_angle`](Self::max_angle) of the [`normal`](Self::normal) of the platform.
+/// Otherwise, the other entity passes through the platform, and it is allowed to keep
+/// passing through it until it is no longer penetrating the platform.
+///
+/// Entities that are already overlapping the platform when they start touching it,
+/// for example because they were spawned inside of it, pass through it as well.
+///
+/// The behavior can be configured for individual entities using [`PassThroughOneWayPlatform`].
+///
+/// Requires the [`OneWayPlatformPlugin`]. See its documentation for an example.
+#[derive(Reflect, Clone, Component, Debug, PartialEq)]
+#[cfg_attr(feature = "serialize", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serialize", reflect(Serialize, Deserialize))]
+#[reflect(Debug, Component, Default, PartialEq)]
+pub struct OneWayPlatform {
+    /// The direction from which other entities collide with the platform,
+    /// expressed in the local space of the collider.
+    ///
+    /// Default: `Vector::Y`
+    pub normal: Vector,
+    /// The maximum angle in radians between the [`normal`](Self::normal) and a contact normal
+    /// for the collision to be accepted.
+    ///
+    /// Default: `PI / 3` (60 degrees)
+    pub max_angle: Scalar,
+    /// The entities that are currently passing through the platform.
+    passing_entities: HashSet<Entity>,
+}
+
+impl Default for OneWayPlatform {
+    fn default() -> Self {
+        Self::new(Vector::Y)
+    }
+}
+
+impl OneWayPlatform {
+    /// Creates a new [`OneWayPlatform`] that other entities collide with from the direction
+    /// of the given `normal`, expressed in the local space of the collider.
+    pub fn new(normal: Vector) -> Self {
+        Self {
+            normal,
+            max_angle: PI / 3.0,
+            passing_entities: HashSet::default(),
+        }
+    }
+
+    /// Sets the maximum angle in radians between the [`normal`](Self::normal) and a contact normal
+    /// for the collision to be accepted.
+    pub fn with_max_angle(mut self, max_angle: Scalar) -> Self {
+        self.max_angle = max_angle;
+        self
+    }
+
+    /// Returns `true` if the given entity is currently passing through the platform.
+    pub fn is_passing_through(&self, entity: Entity) -> bool {
+        self.passing_entities.contains(&entity)
+    }
+
+    /// Returns an iterator over the entities that are currently passing through the platform.
+    pub fn passing_entities(&self) -> impl Iterator<Item = Entity> + '_ {
+        self.passing_entities.iter().copied()
+    }
+
+    /// Returns `true` if a contact with the given normal, pointing out of the platform
+    /// in its local space, should result in a collision.
+    fn accepts_normal(&self, normal: Vector) -> bool {
+        let (Some(normal), Some(platform_normal)) =
+            (normal.try_normalize(), self.normal.try_normalize())
+        else {
+            return false;
+        };
+        normal.dot(platform_normal) >= self.max_angle.cos()
+    }
+}
+
+impl MapEntities for OneWayPlatform {
+    fn map_entities<M: EntityMapper>(&mut self, entity_mapper: &mut M) {
+        self.passing_entities = self
+            .passing_entities
+            .iter()
+            .map(|entity| entity_mapper.map_entity(*entity))
+            .collect();
+    }
+}
+
+/// Determines how an entity interacts with [`OneWayPlatform`]s.
+///
+/// The component can be added to a collider or to the rigid body that the collider is attached to.
+/// Entities without this component use [`PassThroughOneWayPlatform::ByNormal`].
+///
+/// Changing the value wakes up the rigid body if it is [`Sleeping`], so that it can start
+/// falling through a platform it is resting on.
+#[derive(Reflect, Clone, Copy, Component, Debug, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "serialize", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serialize", reflect(Serialize, Deserialize))]
+#[reflect(Debug, Component, Default, PartialEq)]
+pub enum PassThroughOneWayPlatform {
+    /// Passes through a [`OneWayPlatform`] unless the contact normal is within
+    /// the allowed angle of the normal of the platform.
+    #[default]
+    ByNormal,
+    /// Always passes through [`OneWayPlatform`]s, regardless of the contact normal.
+    Always,
+    /// Passes through the next [`OneWayPlatform`] that the entity touches, regardless of
+    /// the contact normal, and then reverts to [`PassThroughOneWayPlatform::ByNormal`].
+    ///
+    /// This can be used to drop down through a platform that the entity is standing on.
+    DropThrough,
+    /// Never passes through [`OneWayPlatform`]s. The platforms behave like normal colliders.
+    ///
+    /// Entities that are already passing through a platform are still allowed to finish passing through it.
+    Never,
+}
+
+/// Wakes up bodies whose [`PassThroughOneWayPlatform`] has changed,
+/// so that sleeping bodies can start falling through platforms.
+fn wake_up_passing_bodies(
+    mut commands: Commands,
+    query: Query<(Entity, Option<&ColliderParent>), Changed<PassThroughOneWayPlatform>>,
+    sleeping_bodies: Query<(), With<Sleeping>>,
+) {
+    for (entity, parent) in &query {
+        let body = parent.map_or(entity, |parent| parent.get());
+        if sleeping_bodies.contains(body) {
+            commands.entity(body).remove::<Sleeping>();
+        }
+    }
+}
+
+/// Forgets the entities passing through [`OneWayPlatform`]s whose collision pair with the platform
+/// has ended in the broad phase, for example because they moved away or were despawned.
+fn prune_passing_entities(
+    mut platforms: Query<&mut OneWayPlatform>,
+    pair_changes: Option<Res<BroadCollisionPairChanges>>,
+) {
+    let Some(pair_changes) = pair_changes else {
+        return;
+    };
+
+    for &(entity1, entity2) in pair_changes.ended() {
+        for (platform_entity, other_entity) in [(entity1, entity2), (entity2, entity1)] {
+            if let Ok(mut platform) = platforms.get_mut(platform_entity) {
+                if platform.passing_entities.contains(&other_entity) {
+                    platform.passing_entities.remove(&other_entity);
+                }
+            }
+        }
+    }
+}
+
+/// Removes the contacts between [`OneWayPlatform`]s and the entities that are passing through them.
+///
+/// Collisions that were already active during the previous frame are kept until contact reporting,
+/// but no longer count as colliding, so that their collision ends normally.
+fn filter_one_way_platform_contacts(
+    mut commands: Commands,
+    mut collisions: ResMut<Collisions>,
+    filter: OneWayPlatformFilter,
+) {
+    if filter.platforms.is_empty() {
+        return;
+    }
+
+    collisions.retain(|contacts| {
+        if !contacts.during_current_frame || filter.keep_contacts(contacts, &mut commands) {
+            return true;
+        }
+        contacts.during_current_frame = false;
+        contacts.during_previous_frame
+    });
+}
+
+/// Determines whether the contacts between [`OneWayPlatform`]s and other entities should be kept.
+///
+/// Changes to the [`passing_entities`](OneWayPlatform::passing_entities) of platforms
+/// and to [`PassThroughOneWayPlatform::DropThrough`] are deferred using [`Commands`].
+#[derive(SystemParam)]
+struct OneWayPlatformFilter<'w, 's> {
+    platforms: Query<'w, 's, &'static OneWayPlatform>,
+    pass_through_query: Query<'w, 's, &'static PassThroughOneWayPlatform, Without<OneWayPlatform>>,
+    velocity_query: Query<'w, 's, &'static LinearVelocity>,
+    time: Res<'w, Time>,
+}
+
+impl OneWayPlatformFilter<'_, '_> {
+    fn keep_contacts(&self, contacts: &Contacts, commands: &mut Commands) -> bool {
+        // Find the platform and the other entity, and the normal pointing out of the platform.
+        let (platform_entity, other_entity, other_body, is_first) =
+            if self.platforms.contains(contacts.entity1) {
+                (
+                    contacts.entity1,
+                    contacts.entity2,
+                    contacts.body_entity2,
+                    true,
+                )
+            } else if self.platforms.contains(contacts.entity2) {
+                (
+                    contacts.entity2,
+                    contacts.entity1,
+                    contacts.body_entity1,
+                    false,
+                )
+            } else {
+                // Neither entity is a platform.
+                return true;
+            };
+        let platform = self.platforms.get(platform_entity).unwrap();
+
+        let max_penetration = contacts
+            .manifolds
+            .iter()
+            .flat_map(|manifold| manifold.contacts.iter())
+            .fold(Scalar::MIN, |max, contact| max.max(contact.penetration));
+        let is_penetrating = max_penetration > 0.0;
+
+        // Use the pass-through mode of the collider, or fall back to the mode of its body.
+        let pass_through_entity = if self.pass_through_query.contains(other_entity) {
+            other_entity
+        } else {
+            other_body.unwrap_or(other_entity)
+        };
+        let pass_through = self.pass_through_query.get(pass_through_entity).ok();
+        let mode = pass_through.copied().unwrap_or_default();
+
+        if platform.passing_entities.contains(&other_entity) {
+            if is_penetrating {
+                // The entity is still passing through the platform.
+                if mode == PassThroughOneWayPlatform::DropThrough {
+                    commands
+                        .entity(pass_through_entity)
+                        .insert(PassThroughOneWayPlatform::ByNormal);
+                }
+                return false;
+            }
+
+            // The entity is no longer penetrating the platform, so forget it.
+            set_passing(commands, platform_entity, other_entity, false);
+        } else if !contacts.during_previous_frame && is_penetrating {
+            // If the contact is new and deeper than the bodies could have moved during the last step,
+            // the entity was already inside the platform, for example because it was spawned there.
+            let velocity = |entity: Option<Entity>| {
+                entity
+                    .and_then(|entity| self.velocity_query.get(entity).ok())
+                    .map_or(Vector::ZERO, |velocity| velocity.0)
+            };
+            let relative_speed =
+                (velocity(contacts.body_entity2) - velocity(contacts.body_entity1)).length();
+
+            if max_penetration > relative_speed * self.time.delta_seconds_adjusted() {
+                set_passing(commands, platform_entity, other_entity, true);
+                return false;
+            }
+        }
+
+        let collide = match mode {
+            PassThroughOneWayPlatform::Never => true,
+            PassThroughOneWayPlatform::Always | PassThroughOneWayPlatform::DropThrough => false,
+            PassThroughOneWayPlatform::ByNormal => contacts.manifolds.iter().all(|manifold| {
+                let normal = if is_first {
+                    manifold.normal1
+                } else {
+                    manifold.normal2
+                };
+                platform.accepts_normal(normal)
+            }),
+        };
+
+        if !collide {
+            set_passing(commands, platform_entity, other_entity, true);
+        }
+
+        collide
+    }
+}
+
+/// Adds or removes `other_entity` from the entities passing through the given platform.
+fn set_passing(commands: &mut Commands, platform: Entity, other_entity: Entity, passing: bool) {
+    commands
+        .entity(platform)
+        .add(move |mut entity: EntityWorldMut| {
+            if let Some(mut platform) = entity.get_mut::<OneWayPlatform>() {
+                if passing {
+                    platform.passing_entities.insert(other_entity);
+                } else {
+                    platform.passing_entities.remove(&other_entity);
+                }
+            }
+        });
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::tests::{box_collider, create_app, create_app_with_hooks, spawn_box, tick_frames};
+
+    #[test]
+    fn one_way_platforms_filter_collisions() {
+        let mut app = create_app();
+
+        app.finish();
+
+        let platform = app
+            .world_mut()
+            .spawn((
+                RigidBody::Static,
+                OneWayPlatform::default(),
+                box_collider(20.0, 0.5),
+            ))
+            .id();
+
+        let mut spawn_body = |position: Vector, velocity: Vector| {
+            let body = spawn_box(&mut app, position);
+            app.world_mut().entity_mut(body).insert((
+                LinearVelocity(velocity),
+                PassThroughOneWayPlatform::default(),
+            ));
+            body
+        };
+
+        let falling_body = spawn_body(Vector::NEG_X * 5.0 + Vector::Y * 2.0, Vector::ZERO);
+        let jumping_body = spawn_body(Vector::Y * -2.0, Vector::Y * 10.0);
+        let overlapping_body = spawn_body(Vector::X * 5.0, Vector::ZERO);
+
+        tick_frames(&mut app, 120);
+
+        let position_y = |app: &App, entity: Entity| app.world().get::<Position>(entity).unwrap().y;
+
+        // The falling body lands on the platform, and the jumping body passes through it from below.
+        assert!(position_y(&app, falling_body) > 0.5);
+        assert!(position_y(&app, jumping_body) > 0.5);
+
+        // The body that started inside of the platform falls through it.
+        assert!(position_y(&app, overlapping_body) < -1.0);
+
+        let passing_entities = |app: &App| {
+            app.world()
+                .get::<OneWayPlatform>(platform)
+                .unwrap()
+                .passing_entities()
+                .collect::<Vec<_>>()
+        };
+
+        // The bodies that passed through the platform are forgotten.
+        assert!(passing_entities(&app).is_empty());
+
+        // Request the body on the platform to drop through it.
+        *app.world_mut()
+            .get_mut::<PassThroughOneWayPlatform>(falling_body)
+            .unwrap() = PassThroughOneWayPlatform::DropThrough;
+
+        tick_frames(&mut app, 5);
+
+        assert_eq!(passing_entities(&app), vec![falling_body]);
+
+        tick_frames(&mut app, 60);
+
+        assert!(position_y(&app, falling_body) < -1.0);
+        assert!(passing_entities(&app).is_empty());
+        assert!(position_y(&app, jumping_body) > 0.5);
+        assert_eq!(
+            app.world().get::<PassThroughOneWayPlatform>(falling_body),
+            Some(&PassThroughOneWayPlatform::ByNormal)
+        );
+    }
+
+    #[test]
+    fn one_way_platforms_work_with_custom_hooks() {
+        /// Hooks that keep all pairs and contacts.
+        #[derive(SystemParam)]
+        struct TestHooks<'w> {
+            _time: Res<'w, Time>,
+        }
+
+        impl CollisionHooks for TestHooks<'_> {}
+
+        let mut app = create_app_with_hooks::<TestHooks>();
+
+        app.finish();
+
+        app.world_mut().spawn((
+            RigidBody::Static,
+            OneWayPlatform::default(),
+            box_collider(20.0, 0.5),
+        ));
+
+        let falling_body = spawn_box(&mut app, Vector::NEG_X * 5.0 + Vector::Y * 2.0);
+        let jumping_body = spawn_box(&mut app, Vector::Y * -2.0);
+        app.world_mut()
+            .entity_mut(jumping_body)
+            .insert(LinearVelocity(Vector::Y * 10.0));
+
+        tick_frames(&mut app, 120);
+
+        let position_y = |entity: Entity| app.world().get::<Position>(entity).unwrap().y;
+        assert!(position_y(falling_body) > 0.5);
+        assert!(position_y(jumping_body) > 0.5);
+    }
+}
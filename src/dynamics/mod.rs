@@ -21,12 +21,13 @@
 //!
 //! ## Plugins
 //!
-//! | Plugin               | Description                                                                                                                           |
-//! | -------------------- | ------------------------------------------------------------------------------------------------------------------------------------- |
-//! | [`IntegratorPlugin`] | Handles motion caused by velocity, and applies external forces and gravity.                                                           |
-//! | [`SolverPlugin`]     | Solves constraints (contacts and joints).                                                                                             |
-//! | [`CcdPlugin`]        | Performs sweep-based [Continuous Collision Detection](dynamics::ccd) for bodies with the [`SweptCcd`] component to prevent tunneling. |
-//! | [`SleepingPlugin`]   | Manages sleeping and waking for bodies, automatically deactivating them to save computational resources.                              |
+//! | Plugin                | Description                                                                                                                           |
+//! | --------------------- | ------------------------------------------------------------------------------------------------------------------------------------- |
+//! | [`IntegratorPlugin`]  | Handles motion caused by velocity, and applies external forces and gravity.                                                           |
+//! | [`SolverPlugin`]      | Solves constraints (contacts and joints).                                                                                             |
+//! | [`CcdPlugin`]         | Performs sweep-based [Continuous Collision Detection](dynamics::ccd) for bodies with the [`SweptCcd`] component to prevent tunneling. |
+//! | [`SleepingPlugin`]    | Manages sleeping and waking for bodies, automatically deactivating them to save computational resources.                              |
+//! | [`WorldBoundsPlugin`] | Detects bodies that leave the [`PhysicsWorldBounds`] and handles them according to an [`OutOfBoundsPolicy`].                          |
 //!
 //! ## Accuracy
 //!
@@ -66,6 +67,7 @@ pub mod integrator;
 pub mod rigid_body;
 pub mod sleeping;
 pub mod solver;
+pub mod world_bounds;
 
 /// Re-exports common types related to the rigid body dynamics functionality.
 pub mod prelude {
@@ -75,6 +77,9 @@ pub mod prelude {
         rigid_body::*,
         sleeping::{DeactivationTime, SleepingPlugin, SleepingThreshold},
         solver::{joints::*, PhysicsLengthUnit, SolverPlugin, SolverSet},
+        world_bounds::{
+            BodyLeftBounds, OutOfBounds, OutOfBoundsPolicy, PhysicsWorldBounds, WorldBoundsPlugin,
+        },
     };
 }
 
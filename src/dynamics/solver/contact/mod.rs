@@ -51,6 +51,22 @@ pub struct ContactConstraintPoint {
     ///
     /// A negative separation indicates penetration.
     pub initial_separation: Scalar,
+
+    /// The effective [`Friction`] at the contact point.
+    pub friction: Friction,
+
+    /// The effective [`Restitution`] at the contact point.
+    pub restitution: Restitution,
+
+    /// The target relative velocity of the second body with respect to the first body
+    /// along the contact surface, expressed in world space.
+    pub tangent_velocity: Vector,
+
+    /// The index of the contact in the [`ContactManifold`] that this point was generated from.
+    ///
+    /// Contacts that are too far apart are skipped when generating the constraint,
+    /// so this may differ from the index of the point in [`ContactConstraint::points`].
+    pub contact_index: usize,
 }
 
 /// A contact constraint used for resolving inter-penetration between two bodies.
@@ -67,9 +83,13 @@ pub struct ContactConstraint {
     pub collider_entity1: Entity,
     /// The entity of the first collider in the contact.
     pub collider_entity2: Entity,
-    /// The combined [`Friction`] of the bodies.
+    /// The [`Friction`] of the [`ContactManifold`].
+    ///
+    /// Individual contact points can override this with [`ContactConstraintPoint::friction`].
     pub friction: Friction,
-    /// The combined [`Restitution`] of the bodies.
+    /// The [`Restitution`] of the [`ContactManifold`].
+    ///
+    /// Individual contact points can override this with [`ContactConstraintPoint::restitution`].
     pub restitution: Restitution,
     /// The world-space contact normal shared by all points in the contact manifold.
     pub normal: Vector,
@@ -93,8 +113,6 @@ impl ContactConstraint {
         collider_transform2: Option<ColliderTransform>,
         collision_margin: impl Into<CollisionMargin>,
         speculative_margin: impl Into<SpeculativeMargin>,
-        friction: Friction,
-        restitution: Restitution,
         softness: SoftnessCoefficients,
         warm_start: bool,
         delta_secs: Scalar,
@@ -115,8 +133,8 @@ impl ContactConstraint {
             entity2: body2.entity,
             collider_entity1,
             collider_entity2,
-            friction,
-            restitution,
+            friction: manifold.friction,
+            restitution: manifold.restitution,
             normal,
             points: Vec::with_capacity(manifold.contacts.len()),
             manifold_index: manifold_id,
@@ -125,7 +143,7 @@ impl ContactConstraint {
         let tangents =
             constraint.tangent_directions(body1.linear_velocity.0, body2.linear_velocity.0);
 
-        for mut contact in manifold.contacts.iter().copied() {
+        for (contact_index, mut contact) in manifold.contacts.iter().copied().enumerate() {
             // Transform contact data from collider-space to body-space.
             if let Some(transform) = collider_transform1 {
                 contact.point1 = transform.rotation * contact.point1 + transform.translation;
@@ -162,6 +180,13 @@ impl ContactConstraint {
                 continue;
             }
 
+            // Use the material properties of the manifold unless they are overridden for this contact.
+            let friction = contact.friction.unwrap_or(manifold.friction);
+            let restitution = contact.restitution.unwrap_or(manifold.restitution);
+            let tangent_velocity = contact
+                .tangent_velocity
+                .unwrap_or(manifold.tangent_velocity);
+
             let point = ContactConstraintPoint {
                 // TODO: Apply warm starting scale here instead of in `warm_start`?
                 normal_part: ContactNormalPart::generate(
@@ -193,6 +218,10 @@ impl ContactConstraint {
                 anchor2: r2,
                 normal_speed: normal.dot(relative_velocity),
                 initial_separation: -contact.penetration - (r2 - r1).dot(normal),
+                friction,
+                restitution,
+                tangent_velocity,
+                contact_index,
             };
 
             constraint.points.push(point);
@@ -307,7 +336,6 @@ impl ContactConstraint {
             }
         }
 
-        let friction = self.friction;
         let tangent_directions =
             self.tangent_directions(body1.linear_velocity.0, body2.linear_velocity.0);
 
@@ -321,14 +349,16 @@ impl ContactConstraint {
             let r1 = point.anchor1;
             let r2 = point.anchor2;
 
-            // Relative velocity at contact point
-            let relative_velocity = body2.velocity_at_point(r2) - body1.velocity_at_point(r1);
+            // Relative velocity at contact point, offset by the target tangent velocity.
+            // The normal component of the target velocity is ignored by the friction part.
+            let relative_velocity =
+                body2.velocity_at_point(r2) - body1.velocity_at_point(r1) - point.tangent_velocity;
 
             // Compute the incremental impulse. The clamping and impulse accumulation is handled by the method.
             let impulse = friction_part.solve_impulse(
                 tangent_directions,
                 relative_velocity,
-                friction,
+                point.friction,
                 point.normal_part.impulse,
             );
 
@@ -374,7 +404,7 @@ impl ContactConstraint {
 
             // Compute the incremental normal impulse to account for restitution.
             let mut impulse = -point.normal_part.effective_mass
-                * (normal_speed + self.restitution.coefficient * point.normal_speed);
+                * (normal_speed + point.restitution.coefficient * point.normal_speed);
 
             // Clamp the accumulated impulse.
             let new_impulse = (point.normal_part.impulse + impulse).max(0.0);
@@ -407,8 +437,9 @@ impl ContactConstraint {
         {
             let force_direction = -self.normal;
             let relative_velocity = velocity1 - velocity2;
+            // Remove the normal component of the relative velocity.
             let tangent_velocity =
-                relative_velocity + force_direction * force_direction.dot(relative_velocity);
+                relative_velocity - force_direction * force_direction.dot(relative_velocity);
 
             let tangent = tangent_velocity
                 .try_normalize()
@@ -425,3 +456,33 @@ impl MapEntities for ContactConstraint {
         self.entity2 = entity_mapper.map_entity(self.entity2);
     }
 }
+
+#[cfg(all(test, feature = "3d"))]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn tangent_directions_are_orthogonal_to_normal() {
+        let constraint = ContactConstraint {
+            entity1: Entity::PLACEHOLDER,
+            entity2: Entity::PLACEHOLDER,
+            collider_entity1: Entity::PLACEHOLDER,
+            collider_entity2: Entity::PLACEHOLDER,
+            friction: Friction::default(),
+            restitution: Restitution::default(),
+            normal: Vector::Y,
+            points: vec![],
+            manifold_index: 0,
+        };
+
+        // The relative velocity has both a normal and a tangential component.
+        let [tangent, bitangent] =
+            constraint.tangent_directions(Vector::new(3.0, -5.0, 0.0), Vector::ZERO);
+
+        // The first tangent follows the tangential part of the relative velocity.
+        assert!(tangent.abs_diff_eq(Vector::X, 1e-6));
+        assert!(tangent.dot(constraint.normal).abs() < 1e-6);
+        assert!(bitangent.dot(constraint.normal).abs() < 1e-6);
+        assert!(bitangent.dot(tangent).abs() < 1e-6);
+    }
+}
@@ -33,6 +33,10 @@ pub struct DistanceJoint {
     pub damping_linear: Scalar,
     /// Angular damping applied by the joint.
     pub damping_angular: Scalar,
+    /// If `true`, the colliders of the bodies connected by the joint can collide with each other.
+    ///
+    /// Default: `false`
+    pub collide_connected: bool,
     /// Lagrange multiplier for the positional correction.
     pub lagrange: Scalar,
     /// The joint's compliance, the inverse of stiffness, has the unit meters / Newton.
@@ -66,6 +70,7 @@ impl Joint for DistanceJoint {
             length_limits: None,
             damping_linear: 0.0,
             damping_angular: 0.0,
+            collide_connected: false,
             lagrange: 0.0,
             compliance: 0.0,
             force: Vector::ZERO,
@@ -119,9 +124,23 @@ impl Joint for DistanceJoint {
     fn damping_angular(&self) -> Scalar {
         self.damping_angular
     }
+
+    fn collide_connected(&self) -> bool {
+        self.collide_connected
+    }
 }
 
 impl DistanceJoint {
+    /// Sets whether the colliders of the bodies connected by the joint can collide with each other.
+    ///
+    /// By default, collisions between the connected bodies are disabled.
+    pub fn with_collide_connected(self, collide_connected: bool) -> Self {
+        Self {
+            collide_connected,
+            ..self
+        }
+    }
+
     /// Constrains the distance the bodies with no constraint on their rotation.
     ///
     /// Returns the force exerted by this constraint.
@@ -30,6 +30,10 @@ pub struct FixedJoint {
     pub damping_linear: Scalar,
     /// Angular damping applied by the joint.
     pub damping_angular: Scalar,
+    /// If `true`, the colliders of the bodies connected by the joint can collide with each other.
+    ///
+    /// Default: `false`
+    pub collide_connected: bool,
     /// Lagrange multiplier for the positional correction.
     pub position_lagrange: Scalar,
     /// Lagrange multiplier for the angular correction caused by the alignment of the bodies.
@@ -87,6 +91,7 @@ impl Joint for FixedJoint {
             local_anchor2: Vector::ZERO,
             damping_linear: 1.0,
             damping_angular: 1.0,
+            collide_connected: false,
             position_lagrange: 0.0,
             align_lagrange: 0.0,
             compliance: 0.0,
@@ -145,9 +150,23 @@ impl Joint for FixedJoint {
     fn damping_angular(&self) -> Scalar {
         self.damping_angular
     }
+
+    fn collide_connected(&self) -> bool {
+        self.collide_connected
+    }
 }
 
 impl FixedJoint {
+    /// Sets whether the colliders of the bodies connected by the joint can collide with each other.
+    ///
+    /// By default, collisions between the connected bodies are disabled.
+    pub fn with_collide_connected(self, collide_connected: bool) -> Self {
+        Self {
+            collide_connected,
+            ..self
+        }
+    }
+
     #[cfg(feature = "2d")]
     fn get_rotation_difference(&self, rot1: &Rotation, rot2: &Rotation) -> Scalar {
         rot1.angle_between(*rot2)
@@ -62,6 +62,14 @@
 //! `with_angular_velocity_damping` methods. Increasing the damping values will cause the velocities
 //! of the connected entities to decrease faster.
 //!
+//! ### Collisions between connected bodies
+//!
+//! By default, the colliders of bodies connected by a joint don't collide with each other.
+//! This prevents contacts from fighting against the joint, for example at the overlapping
+//! anchor points of ragdoll limbs.
+//!
+//! Collisions between the connected bodies can be enabled using the `with_collide_connected` method.
+//!
 //! ### Other configuration
 //!
 //! Different joints may have different configuration options. Many joints allow you to change the axis of allowed
@@ -77,6 +85,14 @@
 //! except you should also implement the [`Joint`] trait's methods. The trait has some useful helper methods
 //! like `align_position` and `align_orientation` to reduce some common boilerplate.
 //!
+//! Instead of adding the [`solve_constraint`] system manually, add the [`JointPlugin`] for the joint type.
+//! It solves the joint, applies its velocity damping, and disables collisions between the connected bodies
+//! unless [`Joint::collide_connected`] is overridden to return `true`.
+//!
+//! ```ignore
+//! app.add_plugins(JointPlugin::<YourJoint>::default());
+//! ```
+//!
 //! Many joints also have joint limits. You can use [`DistanceLimit`] and [`AngleLimit`] to help store these limits
 //! and to compute the current distance from the specified limits.
 //!
@@ -97,8 +113,55 @@ pub use revolute::*;
 #[cfg(feature = "3d")]
 pub use spherical::*;
 
-use crate::{dynamics::solver::xpbd::*, prelude::*};
-use bevy::prelude::*;
+use std::marker::PhantomData;
+
+use crate::{
+    dynamics::solver::{joint_damping, xpbd::*, SubstepSolverSet},
+    prelude::*,
+};
+use bevy::{prelude::*, utils::HashSet};
+
+/// A plugin that adds the systems required by a custom [joint](self) of type `T`.
+///
+/// The joint is solved in [`SubstepSolverSet::SolveUserConstraints`], its velocity damping is applied
+/// in [`SubstepSolverSet::XpbdVelocityProjection`], and collisions between the bodies connected
+/// by the joint are disabled using [`disable_joint_collisions`] unless [`Joint::collide_connected`] returns `true`.
+///
+/// The built-in joints are added by the [`SolverPlugin`], so this plugin is only needed for custom joints.
+/// It must be added after the [`SolverPlugin`].
+pub struct JointPlugin<T: Joint + XpbdConstraint<2>>(PhantomData<T>);
+
+impl<T: Joint + XpbdConstraint<2>> Default for JointPlugin<T> {
+    fn default() -> Self {
+        Self(PhantomData)
+    }
+}
+
+impl<T: Joint + XpbdConstraint<2>> Plugin for JointPlugin<T> {
+    fn build(&self, app: &mut App) {
+        // The exclusions of different joint types can be collected in any order.
+        app.get_schedule_mut(PhysicsSchedule)
+            .expect("add PhysicsSchedule first")
+            .add_systems(
+                disable_joint_collisions::<T>
+                    .in_set(PhysicsStepSet::BroadPhase)
+                    .ambiguous_with(PhysicsStepSet::BroadPhase),
+            );
+
+        // Allowing ambiguities is required so that multiple custom joints can be added.
+        app.get_schedule_mut(SubstepSchedule)
+            .expect("add SubstepSchedule first")
+            .add_systems((
+                solve_constraint::<T, 2>
+                    .in_set(SubstepSolverSet::SolveUserConstraints)
+                    .ambiguous_with(SubstepSolverSet::SolveUserConstraints),
+                joint_damping::<T>
+                    .after(project_angular_velocity)
+                    .in_set(SubstepSolverSet::XpbdVelocityProjection)
+                    .ambiguous_with(SubstepSolverSet::XpbdVelocityProjection),
+            ));
+    }
+}
 
 /// A trait for [joints](self).
 pub trait Joint: Component + PositionConstraint + AngularConstraint {
@@ -132,6 +195,13 @@ pub trait Joint: Component + PositionConstraint + AngularConstraint {
     /// Returns the angular velocity damping of the joint.
     fn damping_angular(&self) -> Scalar;
 
+    /// Returns `true` if the colliders of the bodies connected by the joint can collide with each other.
+    ///
+    /// By default, collisions between the connected bodies are disabled.
+    fn collide_connected(&self) -> bool {
+        false
+    }
+
     /// Applies a positional correction that aligns the positions of the local attachment points `r1` and `r2`.
     ///
     /// Returns the force exerted by the alignment.
@@ -182,6 +252,65 @@ pub trait Joint: Component + PositionConstraint + AngularConstraint {
     }
 }
 
+/// Pairs of rigid bodies connected by [joints](self) that should not collide with each other.
+///
+/// The pairs are collected every frame by the [`disable_joint_collisions`] system of each joint type,
+/// and the [`NarrowPhasePlugin`] skips computing contacts between them. The system is added
+/// by the [`SolverPlugin`] for the built-in joints, and by the [`JointPlugin`] for custom joints.
+#[derive(Resource, Clone, Debug, Default, PartialEq)]
+pub struct JointCollisionExclusions(HashSet<(Entity, Entity)>);
+
+impl JointCollisionExclusions {
+    /// Returns `true` if collisions between the bodies `entity1` and `entity2` are disabled.
+    pub fn contains(&self, entity1: Entity, entity2: Entity) -> bool {
+        self.0.contains(&Self::pair_key(entity1, entity2))
+    }
+
+    /// Disables collisions between the bodies `entity1` and `entity2` until the exclusions are cleared.
+    pub fn insert(&mut self, entity1: Entity, entity2: Entity) {
+        self.0.insert(Self::pair_key(entity1, entity2));
+    }
+
+    /// Returns an iterator over the pairs of bodies that have collisions disabled between them.
+    pub fn iter(&self) -> impl Iterator<Item = &(Entity, Entity)> {
+        self.0.iter()
+    }
+
+    /// Clears all exclusions.
+    pub fn clear(&mut self) {
+        self.0.clear();
+    }
+
+    fn pair_key(entity1: Entity, entity2: Entity) -> (Entity, Entity) {
+        if entity1 < entity2 {
+            (entity1, entity2)
+        } else {
+            (entity2, entity1)
+        }
+    }
+}
+
+/// Clears the [`JointCollisionExclusions`] so that they can be collected again for the current frame.
+pub(crate) fn clear_joint_collision_exclusions(mut exclusions: ResMut<JointCollisionExclusions>) {
+    exclusions.clear();
+}
+
+/// Disables collisions between the bodies connected by joints of type `T`
+/// by adding them to the [`JointCollisionExclusions`].
+///
+/// Joints for which [`Joint::collide_connected`] returns `true` are skipped.
+pub fn disable_joint_collisions<T: Joint + XpbdConstraint<2>>(
+    query: Query<&T>,
+    mut exclusions: ResMut<JointCollisionExclusions>,
+) {
+    for joint in &query {
+        if !joint.collide_connected() {
+            let [entity1, entity2] = joint.entities();
+            exclusions.insert(entity1, entity2);
+        }
+    }
+}
+
 /// A limit that indicates that the distance between two points should be between `min` and `max`.
 #[derive(Clone, Copy, Debug, PartialEq, Reflect)]
 #[cfg_attr(feature = "serialize", derive(serde::Serialize, serde::Deserialize))]
@@ -33,6 +33,10 @@ pub struct PrismaticJoint {
     pub damping_linear: Scalar,
     /// Angular damping applied by the joint.
     pub damping_angular: Scalar,
+    /// If `true`, the colliders of the bodies connected by the joint can collide with each other.
+    ///
+    /// Default: `false`
+    pub collide_connected: bool,
     /// Lagrange multiplier for the positional correction.
     pub position_lagrange: Scalar,
     /// Lagrange multiplier for the angular correction caused by the alignment of the bodies.
@@ -82,6 +86,7 @@ impl Joint for PrismaticJoint {
             free_axis_limits: None,
             damping_linear: 1.0,
             damping_angular: 1.0,
+            collide_connected: false,
             position_lagrange: 0.0,
             align_lagrange: 0.0,
             compliance: 0.0,
@@ -140,9 +145,23 @@ impl Joint for PrismaticJoint {
     fn damping_angular(&self) -> Scalar {
         self.damping_angular
     }
+
+    fn collide_connected(&self) -> bool {
+        self.collide_connected
+    }
 }
 
 impl PrismaticJoint {
+    /// Sets whether the colliders of the bodies connected by the joint can collide with each other.
+    ///
+    /// By default, collisions between the connected bodies are disabled.
+    pub fn with_collide_connected(self, collide_connected: bool) -> Self {
+        Self {
+            collide_connected,
+            ..self
+        }
+    }
+
     /// Constrains the relative positions of the bodies, only allowing translation along one free axis.
     ///
     /// Returns the force exerted by this constraint.
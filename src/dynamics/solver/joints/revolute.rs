@@ -39,6 +39,10 @@ pub struct RevoluteJoint {
     pub damping_linear: Scalar,
     /// Angular damping applied by the joint.
     pub damping_angular: Scalar,
+    /// If `true`, the colliders of the bodies connected by the joint can collide with each other.
+    ///
+    /// Default: `false`
+    pub collide_connected: bool,
     /// Lagrange multiplier for the positional correction.
     pub position_lagrange: Scalar,
     /// Lagrange multiplier for the angular correction caused by the alignment of the bodies.
@@ -109,6 +113,7 @@ impl Joint for RevoluteJoint {
             angle_limit: None,
             damping_linear: 1.0,
             damping_angular: 1.0,
+            collide_connected: false,
             position_lagrange: 0.0,
             align_lagrange: 0.0,
             angle_limit_lagrange: 0.0,
@@ -172,9 +177,23 @@ impl Joint for RevoluteJoint {
     fn damping_angular(&self) -> Scalar {
         self.damping_angular
     }
+
+    fn collide_connected(&self) -> bool {
+        self.collide_connected
+    }
 }
 
 impl RevoluteJoint {
+    /// Sets whether the colliders of the bodies connected by the joint can collide with each other.
+    ///
+    /// By default, collisions between the connected bodies are disabled.
+    pub fn with_collide_connected(self, collide_connected: bool) -> Self {
+        Self {
+            collide_connected,
+            ..self
+        }
+    }
+
     /// Sets the axis that the bodies should be aligned on.
     #[cfg(feature = "3d")]
     pub fn with_aligned_axis(self, axis: Vector) -> Self {
@@ -37,6 +37,10 @@ pub struct SphericalJoint {
     pub damping_linear: Scalar,
     /// Angular damping applied by the joint.
     pub damping_angular: Scalar,
+    /// If `true`, the colliders of the bodies connected by the joint can collide with each other.
+    ///
+    /// Default: `false`
+    pub collide_connected: bool,
     /// Lagrange multiplier for the positional correction.
     pub position_lagrange: Scalar,
     /// Lagrange multiplier for the angular correction caused by the swing limits.
@@ -102,6 +106,7 @@ impl Joint for SphericalJoint {
             twist_limit: None,
             damping_linear: 1.0,
             damping_angular: 1.0,
+            collide_connected: false,
             position_lagrange: 0.0,
             swing_lagrange: 0.0,
             twist_lagrange: 0.0,
@@ -165,9 +170,23 @@ impl Joint for SphericalJoint {
     fn damping_angular(&self) -> Scalar {
         self.damping_angular
     }
+
+    fn collide_connected(&self) -> bool {
+        self.collide_connected
+    }
 }
 
 impl SphericalJoint {
+    /// Sets whether the colliders of the bodies connected by the joint can collide with each other.
+    ///
+    /// By default, collisions between the connected bodies are disabled.
+    pub fn with_collide_connected(self, collide_connected: bool) -> Self {
+        Self {
+            collide_connected,
+            ..self
+        }
+    }
+
     /// Sets the limits of the allowed relative rotation around the `swing_axis`.
     pub fn with_swing_limits(self, min: Scalar, max: Scalar) -> Self {
         Self {
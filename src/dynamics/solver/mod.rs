@@ -72,7 +72,8 @@ impl Plugin for SolverPlugin {
     fn build(&self, app: &mut App) {
         app.init_resource::<SolverConfig>()
             .init_resource::<ContactSoftnessCoefficients>()
-            .init_resource::<ContactConstraints>();
+            .init_resource::<ContactConstraints>()
+            .init_resource::<JointCollisionExclusions>();
 
         if !app.world().contains_resource::<PhysicsLengthUnit>() {
             app.insert_resource(PhysicsLengthUnit(self.length_unit));
@@ -85,6 +86,21 @@ impl Plugin for SolverPlugin {
 
         physics.add_systems(update_contact_softness.before(PhysicsStepSet::NarrowPhase));
 
+        // Collect the bodies connected by joints that should not collide with each other.
+        physics.add_systems(joints::clear_joint_collision_exclusions.in_set(PhysicsStepSet::First));
+        physics.add_systems(
+            (
+                disable_joint_collisions::<FixedJoint>,
+                disable_joint_collisions::<RevoluteJoint>,
+                #[cfg(feature = "3d")]
+                disable_joint_collisions::<SphericalJoint>,
+                disable_joint_collisions::<PrismaticJoint>,
+                disable_joint_collisions::<DistanceJoint>,
+            )
+                .chain()
+                .in_set(PhysicsStepSet::BroadPhase),
+        );
+
         // See `SolverSet` for what each system set is responsible for.
         physics.configure_sets(
             (
@@ -590,9 +606,13 @@ fn solve_restitution(
     let threshold = solver_config.restitution_threshold * length_unit.0;
 
     for constraint in constraints.iter_mut() {
-        let restitution = constraint.restitution.coefficient;
-
-        if restitution == 0.0 {
+        // Skip constraints without restitution.
+        // The coefficient can be overridden for individual contact points.
+        if constraint
+            .points
+            .iter()
+            .all(|point| point.restitution.coefficient == 0.0)
+        {
             continue;
         }
 
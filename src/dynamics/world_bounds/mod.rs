@@ -0,0 +1,362 @@
+//! Detects bodies that leave the bounds of the physics world and handles them according to an [`OutOfBoundsPolicy`].
+//!
+//! See [`WorldBoundsPlugin`].
+
+use crate::{collision::broad_phase::NonFiniteAabb, prelude::*};
+use bevy::{prelude::*, utils::HashSet};
+
+/// Detects bodies that leave the [`PhysicsWorldBounds`], sends a [`BodyLeftBounds`] event for them,
+/// and handles them according to the configured [`OutOfBoundsPolicy`].
+///
+/// Without bounds, bodies that fall off the world keep moving forever. Eventually, their positions
+/// and [`ColliderAabb`]s can become so large that they are no longer finite, at which point
+/// they are removed from the broad phase. With bounds, such bodies are also handled
+/// according to the [`OutOfBoundsPolicy`].
+///
+/// The bounds are only checked if the [`PhysicsWorldBounds`] resource exists:
+///
+/// ```no_run
+#[cfg_attr(feature = "2d", doc = "use avian2d::{math::Vector, prelude::*};")]
+#[cfg_attr(feature = "3d", doc = "use avian3d::{math::Vector, prelude::*};")]
+/// use bevy::prelude::*;
+///
+/// fn main() {
+///     App::new()
+///         .add_plugins((DefaultPlugins, PhysicsPlugins::default()))
+///         .insert_resource(PhysicsWorldBounds::new(
+///             Vector::splat(-1000.0),
+///             Vector::splat(1000.0),
+///             OutOfBoundsPolicy::Despawn,
+///         ))
+///         .add_systems(Update, print_out_of_bounds_bodies)
+///         .run();
+/// }
+///
+/// fn print_out_of_bounds_bodies(mut events: EventReader<BodyLeftBounds>) {
+///     for event in events.read() {
+///         println!("{:?} left the world bounds at {}", event.entity, event.position);
+///     }
+/// }
+/// ```
+///
+/// The bounds are checked in [`PhysicsStepSet::Last`].
+pub struct WorldBoundsPlugin;
+
+impl Plugin for WorldBoundsPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_event::<BodyLeftBounds>()
+            .register_type::<PhysicsWorldBounds>()
+            .register_type::<OutOfBounds>();
+
+        let physics_schedule = app
+            .get_schedule_mut(PhysicsSchedule)
+            .expect("add PhysicsSchedule first");
+
+        physics_schedule.add_systems(
+            handle_out_of_bounds_bodies
+                .run_if(resource_exists::<PhysicsWorldBounds>)
+                .in_set(PhysicsStepSet::Last),
+        );
+    }
+}
+
+/// The axis-aligned bounds of the physics world.
+///
+/// When the [`Position`] of a dynamic or kinematic [rigid body](RigidBody) leaves the bounds,
+/// a [`BodyLeftBounds`] event is sent, and the body is handled according to the [`OutOfBoundsPolicy`].
+/// Bodies with a non-finite position or a collider with a non-finite [`ColliderAabb`]
+/// are always considered to be out of bounds.
+///
+/// The bounds are not checked if this resource doesn't exist, which is the default.
+///
+/// See [`WorldBoundsPlugin`] for an example.
+#[derive(Resource, Reflect, Clone, Copy, Debug, PartialEq)]
+#[cfg_attr(feature = "serialize", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serialize", reflect(Serialize, Deserialize))]
+#[reflect(Debug, Resource, PartialEq)]
+pub struct PhysicsWorldBounds {
+    /// The minimum corner of the bounds.
+    pub min: Vector,
+    /// The maximum corner of the bounds.
+    pub max: Vector,
+    /// Determines what happens to bodies that leave the bounds.
+    pub policy: OutOfBoundsPolicy,
+}
+
+impl PhysicsWorldBounds {
+    /// Creates new [`PhysicsWorldBounds`] with the given minimum and maximum corners and [`OutOfBoundsPolicy`].
+    pub const fn new(min: Vector, max: Vector, policy: OutOfBoundsPolicy) -> Self {
+        Self { min, max, policy }
+    }
+
+    /// Returns `true` if the given point is inside the bounds.
+    ///
+    /// Non-finite points are never inside the bounds.
+    pub fn contains(&self, point: Vector) -> bool {
+        point.is_finite() && point.cmpge(self.min).all() && point.cmple(self.max).all()
+    }
+}
+
+/// Determines what happens to bodies that leave the [`PhysicsWorldBounds`].
+#[derive(Reflect, Clone, Copy, Debug, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "serialize", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serialize", reflect(Serialize, Deserialize))]
+#[reflect(Debug, PartialEq)]
+pub enum OutOfBoundsPolicy {
+    /// The body is despawned recursively.
+    Despawn,
+    /// The body is marked as [`Sleeping`] and its velocity is reset.
+    ///
+    /// Resetting the velocity doesn't wake the body up, but it can be woken up again,
+    /// for example when its velocity is changed or another body hits it.
+    Sleep,
+    /// The body is turned into a [static](RigidBody::Static) body and its velocity is reset.
+    Freeze,
+    /// The body is not modified, and only the [`BodyLeftBounds`] event is sent.
+    #[default]
+    Report,
+}
+
+/// An event that is sent when a [rigid body](RigidBody) leaves the [`PhysicsWorldBounds`].
+///
+/// The event is only sent once when the body leaves the bounds. If the body stays alive
+/// and returns inside the bounds, the event can be sent again the next time it leaves them.
+///
+/// See [`WorldBoundsPlugin`] for an example.
+#[derive(Event, Clone, Copy, Debug, PartialEq)]
+#[cfg_attr(feature = "serialize", derive(serde::Serialize, serde::Deserialize))]
+pub struct BodyLeftBounds {
+    /// The body that left the bounds.
+    pub entity: Entity,
+    /// The position of the body when it was detected to be out of bounds.
+    pub position: Vector,
+    /// The policy that was applied to the body.
+    pub policy: OutOfBoundsPolicy,
+}
+
+/// A marker component for [rigid bodies](RigidBody) that are outside the [`PhysicsWorldBounds`].
+///
+/// Added and removed automatically by the [`WorldBoundsPlugin`].
+#[derive(Reflect, Clone, Copy, Component, Debug, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "serialize", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serialize", reflect(Serialize, Deserialize))]
+#[reflect(Debug, Component, Default, PartialEq)]
+pub struct OutOfBounds;
+
+/// Sends [`BodyLeftBounds`] events for bodies that have left the [`PhysicsWorldBounds`]
+/// and applies the [`OutOfBoundsPolicy`] to them.
+#[allow(clippy::type_complexity)]
+fn handle_out_of_bounds_bodies(
+    mut commands: Commands,
+    mut bodies: Query<(
+        Entity,
+        &mut RigidBody,
+        &Position,
+        Option<&mut LinearVelocity>,
+        Option<&mut AngularVelocity>,
+        Has<OutOfBounds>,
+    )>,
+    non_finite_colliders: Query<&ColliderParent, With<NonFiniteAabb>>,
+    bounds: Res<PhysicsWorldBounds>,
+    mut events: EventWriter<BodyLeftBounds>,
+) {
+    let policy = bounds.policy;
+
+    // Colliders with non-finite AABBs are removed from the broad phase,
+    // so their bodies are handled as if they were out of bounds.
+    let non_finite_bodies: HashSet<Entity> = non_finite_colliders
+        .iter()
+        .map(ColliderParent::get)
+        .collect();
+
+    for (entity, mut rb, position, lin_vel, ang_vel, was_out_of_bounds) in &mut bodies {
+        if rb.is_static() {
+            continue;
+        }
+
+        if bounds.contains(position.0) && !non_finite_bodies.contains(&entity) {
+            if was_out_of_bounds {
+                commands.entity(entity).remove::<OutOfBounds>();
+            }
+            continue;
+        }
+
+        // The event has already been sent for this body.
+        if was_out_of_bounds {
+            continue;
+        }
+
+        events.send(BodyLeftBounds {
+            entity,
+            position: position.0,
+            policy,
+        });
+
+        match policy {
+            OutOfBoundsPolicy::Despawn => {
+                commands.entity(entity).despawn_recursive();
+                continue;
+            }
+            OutOfBoundsPolicy::Sleep => {
+                commands.entity(entity).try_insert(Sleeping);
+
+                // Changing the velocity would wake the body up again.
+                if let Some(mut lin_vel) = lin_vel {
+                    *lin_vel.bypass_change_detection() = LinearVelocity::ZERO;
+                }
+                if let Some(mut ang_vel) = ang_vel {
+                    *ang_vel.bypass_change_detection() = AngularVelocity::ZERO;
+                }
+            }
+            OutOfBoundsPolicy::Freeze => {
+                *rb = RigidBody::Static;
+
+                if let Some(mut lin_vel) = lin_vel {
+                    *lin_vel = LinearVelocity::ZERO;
+                }
+                if let Some(mut ang_vel) = ang_vel {
+                    *ang_vel = AngularVelocity::ZERO;
+                }
+            }
+            OutOfBoundsPolicy::Report => {}
+        }
+
+        commands.entity(entity).try_insert(OutOfBounds);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::tests::{box_collider, create_app, tick_60_fps};
+
+    #[test]
+    fn bodies_leaving_world_bounds_are_handled() {
+        let mut app = create_app();
+
+        app.insert_resource(PhysicsWorldBounds::new(
+            Vector::splat(-10.0),
+            Vector::splat(10.0),
+            OutOfBoundsPolicy::Despawn,
+        ));
+
+        app.finish();
+
+        let inside = app
+            .world_mut()
+            .spawn((RigidBody::Dynamic, Position(Vector::ZERO)))
+            .id();
+        let outside = app
+            .world_mut()
+            .spawn((RigidBody::Dynamic, Position(Vector::X * 20.0)))
+            .id();
+        let static_outside = app
+            .world_mut()
+            .spawn((RigidBody::Static, Position(Vector::X * 20.0)))
+            .id();
+
+        tick_60_fps(&mut app);
+
+        let events = app.world().resource::<Events<BodyLeftBounds>>();
+        let left: Vec<Entity> = events
+            .iter_current_update_events()
+            .map(|event| event.entity)
+            .collect();
+        assert_eq!(left, vec![outside]);
+
+        assert!(app.world().get_entity(inside).is_some());
+        assert!(app.world().get_entity(outside).is_none());
+        assert!(app.world().get_entity(static_outside).is_some());
+
+        // With the `Freeze` policy, the body is made static and is not reported again.
+        app.world_mut().resource_mut::<PhysicsWorldBounds>().policy = OutOfBoundsPolicy::Freeze;
+        app.world_mut().get_mut::<Position>(inside).unwrap().0 = Vector::NEG_Y * 20.0;
+
+        tick_60_fps(&mut app);
+
+        assert_eq!(
+            app.world().get::<RigidBody>(inside),
+            Some(&RigidBody::Static)
+        );
+        assert!(app.world().get::<OutOfBounds>(inside).is_some());
+
+        // With the `Sleep` policy, the body is put to sleep and its velocity is reset
+        // without waking it up again.
+        app.world_mut().resource_mut::<PhysicsWorldBounds>().policy = OutOfBoundsPolicy::Sleep;
+        let sleeper = app
+            .world_mut()
+            .spawn((
+                RigidBody::Dynamic,
+                Position(Vector::Y * 9.0),
+                LinearVelocity(Vector::Y * 5.0),
+            ))
+            .id();
+
+        for _ in 0..60 {
+            if app.world().get::<OutOfBounds>(sleeper).is_some() {
+                break;
+            }
+            tick_60_fps(&mut app);
+        }
+        assert!(app.world().get::<OutOfBounds>(sleeper).is_some());
+        tick_60_fps(&mut app);
+        tick_60_fps(&mut app);
+
+        assert!(app.world().get::<Sleeping>(sleeper).is_some());
+        assert!(app.world().get::<OutOfBounds>(sleeper).is_some());
+        assert_eq!(
+            app.world().get::<LinearVelocity>(sleeper),
+            Some(&LinearVelocity::ZERO)
+        );
+    }
+
+    #[cfg(all(
+        feature = "default-collider",
+        any(feature = "parry-f32", feature = "parry-f64")
+    ))]
+    #[test]
+    fn bodies_with_non_finite_aabbs_are_out_of_bounds() {
+        let mut app = create_app();
+
+        app.insert_resource(PhysicsWorldBounds::new(
+            Vector::splat(-10.0),
+            Vector::splat(10.0),
+            OutOfBoundsPolicy::Report,
+        ));
+
+        app.finish();
+
+        // Simulate a collider whose AABB has grown so large that it is no longer finite.
+        // The body doesn't move, so its AABB is only recomputed on the first step.
+        app.get_schedule_mut(PhysicsSchedule).unwrap().add_systems(
+            (|mut aabbs: Query<&mut ColliderAabb>| {
+                for mut aabb in &mut aabbs {
+                    aabb.max = Vector::splat(Scalar::INFINITY);
+                }
+            })
+            .in_set(crate::collision::broad_phase::BroadPhaseSet::First)
+            .ambiguous_with_all(),
+        );
+
+        let body = app
+            .world_mut()
+            .spawn((RigidBody::Kinematic, box_collider(1.0, 1.0)))
+            .id();
+
+        let mut reader = app
+            .world()
+            .resource::<Events<BodyLeftBounds>>()
+            .get_reader();
+        let mut left = vec![];
+
+        for _ in 0..3 {
+            tick_60_fps(&mut app);
+            let events = app.world().resource::<Events<BodyLeftBounds>>();
+            left.extend(reader.read(events).map(|event| event.entity));
+        }
+
+        assert!(app.world().get::<Position>(body).unwrap().is_finite());
+        assert_eq!(left, vec![body]);
+        assert!(app.world().get::<OutOfBounds>(body).is_some());
+    }
+}
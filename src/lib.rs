@@ -475,12 +475,20 @@ pub mod prelude {
     pub use crate::{
         collision::{
             self,
-            broad_phase::{BroadCollisionPairs, BroadPhasePlugin},
+            broad_phase::{
+                BroadCollisionPairChanges, BroadCollisionPairs, BroadPhasePlugin,
+                CollisionExclusions, DynamicTreeBroadPhaseConfig, DynamicTreeBroadPhasePlugin,
+                SpatialHashBroadPhaseConfig, SpatialHashBroadPhasePlugin,
+            },
             collider::{ColliderBackendPlugin, ColliderHierarchyPlugin},
             contact_reporting::{
-                Collision, CollisionEnded, CollisionStarted, ContactReportingPlugin,
+                Collision, CollisionEnded, CollisionEventsEnabled, CollisionImpact,
+                CollisionStarted, ContactForceEvent, ContactForceEventThreshold,
+                ContactReportingConfig, ContactReportingPlugin, OnCollisionEnd, OnCollisionStart,
             },
-            narrow_phase::{NarrowPhaseConfig, NarrowPhasePlugin},
+            hooks::CollisionHooks,
+            narrow_phase::{ContactCacheTolerance, NarrowPhaseConfig, NarrowPhasePlugin},
+            one_way_platform::{OneWayPlatform, OneWayPlatformPlugin, PassThroughOneWayPlatform},
             *,
         },
         dynamics::{self, ccd::SpeculativeMargin, prelude::*},
@@ -526,10 +534,12 @@ use prelude::*;
 /// | [`BroadPhasePlugin`]              | Collects pairs of potentially colliding entities into [`BroadCollisionPairs`] using [AABB](ColliderAabb) intersection checks.                              |
 /// | [`NarrowPhasePlugin`]             | Computes contacts between entities and sends collision events.                                                                                             |
 /// | [`ContactReportingPlugin`]        | Sends collision events and updates [`CollidingEntities`].                                                                                                  |
+/// | [`OneWayPlatformPlugin`]          | Filters collisions with [`OneWayPlatform`]s, allowing bodies to pass through them from one side.                                                           |
 /// | [`IntegratorPlugin`]              | Handles motion caused by velocity, and applies external forces and gravity.                                                                                |
 /// | [`SolverPlugin`]                  | Manages and solves contacts, [joints](dynamics::solver::joints), and other constraints.                                                                    |
 /// | [`CcdPlugin`]                     | Performs sweep-based [Continuous Collision Detection](dynamics::ccd) for bodies with the [`SweptCcd`] component.                                           |
 /// | [`SleepingPlugin`]                | Manages sleeping and waking for bodies, automatically deactivating them to save computational resources.                                                   |
+/// | [`WorldBoundsPlugin`]             | Detects bodies that leave the [`PhysicsWorldBounds`] and handles them according to an [`OutOfBoundsPolicy`].                                               |
 /// | [`SpatialQueryPlugin`]            | Handles spatial queries like [raycasting](spatial_query#raycasting) and [shapecasting](spatial_query#shapecasting).                                        |
 /// | [`SyncPlugin`]                    | Keeps [`Position`] and [`Rotation`] in sync with `Transform`.                                                                                              |
 /// | [`PhysicsDebugPlugin`]            | Renders physics objects and events like [AABBs](ColliderAabb) and [contacts](Collision) for debugging purposes (only with `debug-plugin` feature enabled). |
@@ -733,10 +743,12 @@ impl PluginGroup for PhysicsPlugins {
         builder
             .add(BroadPhasePlugin)
             .add(ContactReportingPlugin)
+            .add(OneWayPlatformPlugin)
             .add(IntegratorPlugin::default())
             .add(SolverPlugin::new_with_length_unit(self.length_unit))
             .add(CcdPlugin::new(self.schedule))
             .add(SleepingPlugin)
+            .add(WorldBoundsPlugin)
             .add(SpatialQueryPlugin::new(self.schedule))
             .add(SyncPlugin::new(self.schedule))
     }
@@ -224,7 +224,17 @@ impl Plugin for SpatialQueryPlugin {
             feature = "default-collider",
             any(feature = "parry-f32", feature = "parry-f64")
         ))]
-        app.init_resource::<SpatialQueryPipeline>();
+        {
+            app.init_resource::<SpatialQueryPipeline>();
+
+            // Remove colliders from the pipeline as soon as they are removed or despawned.
+            app.observe(
+                |trigger: Trigger<OnRemove, Collider>,
+                 mut query_pipeline: ResMut<SpatialQueryPipeline>| {
+                    query_pipeline.remove_collider(trigger.entity());
+                },
+            );
+        }
 
         app.add_systems(self.schedule, init_ray_hits.in_set(PrepareSet::PreInit));
 
@@ -1,11 +1,14 @@
 use std::sync::Arc;
 
-use crate::prelude::*;
-use bevy::{prelude::*, utils::HashMap};
+use crate::{collision::collider::subshape_index_at_local_point, prelude::*};
+use bevy::{
+    prelude::*,
+    utils::{Entry, HashMap},
+};
 use parry::{
     bounding_volume::Aabb,
-    math::Isometry,
-    partitioning::Qbvh,
+    math::{Isometry, Vector as ParryVector},
+    partitioning::{Qbvh, QbvhUpdateWorkspace},
     query::{
         details::{
             NormalConstraints, RayCompositeShapeToiAndNormalBestFirstVisitor,
@@ -15,21 +18,35 @@ use parry::{
         visitors::{
             BoundingVolumeIntersectionsVisitor, PointIntersectionsVisitor, RayIntersectionsVisitor,
         },
-        DefaultQueryDispatcher, QueryDispatcher, ShapeCastOptions,
+        DefaultQueryDispatcher, QueryDispatcher, Ray, RayCast, RayIntersection, ShapeCastHit,
+        ShapeCastOptions,
     },
-    shape::{Shape, TypedSimdCompositeShape},
+    shape::{FeatureId, HeightField, Shape, SharedShape, TypedShape, TypedSimdCompositeShape},
 };
 
+/// The margin by which the nodes of the [`SpatialQueryPipeline`] `Qbvh` are loosened.
+const QBVH_MARGIN: Scalar = 0.01;
+
 /// A resource for the spatial query pipeline.
 ///
 /// The pipeline maintains a quaternary bounding volume hierarchy `Qbvh` of the world's colliders
 /// as an acceleration structure for spatial queries.
+///
+/// The hierarchy is updated incrementally once per physics frame in [`PhysicsStepSet::SpatialQuery`]:
+/// only colliders that were added, removed, or changed are inserted, removed, or refitted.
+/// The leaf bounds reuse the [`ColliderAabb`]s computed by the broad phase.
 #[derive(Resource, Clone)]
 pub struct SpatialQueryPipeline {
     pub(crate) qbvh: Qbvh<u32>,
     pub(crate) dispatcher: Arc<dyn QueryDispatcher>,
-    pub(crate) colliders: HashMap<Entity, (Isometry<Scalar>, Collider, CollisionLayers)>,
+    pub(crate) colliders: HashMap<Entity, (Isometry<Scalar>, SharedShape, CollisionLayers)>,
     pub(crate) entity_generations: HashMap<u32, u32>,
+    /// The leaf AABBs of the `Qbvh`, keyed by entity index.
+    pub(crate) leaf_aabbs: HashMap<u32, Aabb>,
+    /// Scratch space reused across incremental `Qbvh` updates.
+    workspace: QbvhUpdateWorkspace,
+    /// True if leaves have been inserted, updated, or removed since the last refit.
+    is_dirty: bool,
 }
 
 impl Default for SpatialQueryPipeline {
@@ -39,6 +56,9 @@ impl Default for SpatialQueryPipeline {
             dispatcher: Arc::new(DefaultQueryDispatcher),
             colliders: HashMap::default(),
             entity_generations: HashMap::default(),
+            leaf_aabbs: HashMap::default(),
+            workspace: QbvhUpdateWorkspace::default(),
+            is_dirty: false,
         }
     }
 }
@@ -64,7 +84,7 @@ impl SpatialQueryPipeline {
         &'a self,
         query_filter: SpatialQueryFilter,
         predicate: &'a dyn Fn(Entity) -> bool,
-    ) -> QueryPipelineAsCompositeShapeWithPredicate {
+    ) -> QueryPipelineAsCompositeShapeWithPredicate<'a, 'a> {
         QueryPipelineAsCompositeShapeWithPredicate {
             pipeline: self,
             colliders: &self.colliders,
@@ -74,6 +94,10 @@ impl SpatialQueryPipeline {
     }
 
     /// Updates the associated acceleration structures with a new set of entities.
+    ///
+    /// This clears the pipeline and rebuilds it from scratch. The pipeline is kept up to date
+    /// incrementally by [`SpatialQuery::update_pipeline`], so this is mostly useful for
+    /// pipelines that are managed manually.
     pub fn update<'a>(
         &mut self,
         colliders: impl Iterator<
@@ -93,7 +117,7 @@ impl SpatialQueryPipeline {
                     entity,
                     (
                         make_isometry(position.0, *rotation),
-                        collider.clone(),
+                        collider.shape_scaled().clone(),
                         layers.map_or(CollisionLayers::default(), |layers| *layers),
                     ),
                 )
@@ -105,7 +129,7 @@ impl SpatialQueryPipeline {
 
     fn update_internal(
         &mut self,
-        colliders: HashMap<Entity, (Isometry<Scalar>, Collider, CollisionLayers)>,
+        colliders: HashMap<Entity, (Isometry<Scalar>, SharedShape, CollisionLayers)>,
         added: impl Iterator<Item = Entity>,
     ) {
         self.colliders = colliders;
@@ -120,9 +144,14 @@ impl SpatialQueryPipeline {
             }
         }
 
-        struct DataGenerator<'a>(
-            &'a HashMap<Entity, (Isometry<Scalar>, Collider, CollisionLayers)>,
-        );
+        // Compute the leaf AABBs. They are also needed for later incremental refits.
+        self.leaf_aabbs = self
+            .colliders
+            .iter()
+            .map(|(entity, (iso, shape, _))| (entity.index(), shape.compute_aabb(iso)))
+            .collect();
+
+        struct DataGenerator<'a>(&'a HashMap<u32, Aabb>);
 
         impl<'a> parry::partitioning::QbvhDataGenerator<u32> for DataGenerator<'a> {
             fn size_hint(&self) -> usize {
@@ -131,23 +160,177 @@ impl SpatialQueryPipeline {
 
             #[inline(always)]
             fn for_each(&mut self, mut f: impl FnMut(u32, parry::bounding_volume::Aabb)) {
-                for (entity, co) in self.0.iter() {
-                    // Compute and return AABB
-                    let (iso, shape, _) = co;
-                    let aabb = shape.shape_scaled().compute_aabb(iso);
-                    f(entity.index(), aabb)
+                for (index, aabb) in self.0.iter() {
+                    f(*index, *aabb)
+                }
+            }
+        }
+
+        self.qbvh
+            .clear_and_rebuild(DataGenerator(&self.leaf_aabbs), QBVH_MARGIN);
+        self.is_dirty = false;
+    }
+
+    /// Inserts a collider into the pipeline, or updates it if it is already present.
+    ///
+    /// The collider shape is only stored again if the collider is new or `collider_changed` is true.
+    /// `aabb` is the collider's [`ColliderAabb`] computed by the broad phase, if it is up to date
+    /// with the given position and rotation. It is reused as the leaf bounds when present,
+    /// and otherwise a new AABB is computed.
+    ///
+    /// The change only takes effect in the acceleration structure after calling [`Self::refit`].
+    #[allow(clippy::too_many_arguments)]
+    pub(crate) fn insert_or_update_collider(
+        &mut self,
+        entity: Entity,
+        position: &Position,
+        rotation: &Rotation,
+        collider: &Collider,
+        collider_changed: bool,
+        layers: CollisionLayers,
+        aabb: Option<ColliderAabb>,
+    ) {
+        let isometry = make_isometry(position.0, *rotation);
+
+        let leaf_aabb = aabb
+            .filter(|aabb| aabb.min.is_finite() && aabb.max.is_finite())
+            .unwrap_or_else(|| collider.aabb(position.0, *rotation));
+
+        match self.colliders.entry(entity) {
+            Entry::Occupied(mut entry) => {
+                let (old_isometry, old_shape, old_layers) = entry.get_mut();
+                *old_isometry = isometry;
+                *old_layers = layers;
+                if collider_changed {
+                    *old_shape = collider.shape_scaled().clone();
                 }
             }
+            Entry::Vacant(entry) => {
+                entry.insert((isometry, collider.shape_scaled().clone(), layers));
+            }
+        }
+
+        let index = entity.index();
+        self.entity_generations.insert(index, entity.generation());
+        self.leaf_aabbs.insert(
+            index,
+            Aabb {
+                mins: leaf_aabb.min.into(),
+                maxs: leaf_aabb.max.into(),
+            },
+        );
+        self.qbvh.pre_update_or_insert(index);
+        self.is_dirty = true;
+    }
+
+    /// Removes a collider from the pipeline.
+    ///
+    /// The change only takes effect in the acceleration structure after calling [`Self::refit`].
+    pub(crate) fn remove_collider(&mut self, entity: Entity) {
+        if self.colliders.remove(&entity).is_none() {
+            return;
+        }
+
+        // The entity index may already have been reused by a newer collider.
+        let index = entity.index();
+        if self.entity_generations.get(&index) == Some(&entity.generation()) {
+            self.entity_generations.remove(&index);
+            self.leaf_aabbs.remove(&index);
+            self.qbvh.remove(index);
+            self.is_dirty = true;
         }
+    }
 
+    /// Refits and rebalances the acceleration structure after colliders have been
+    /// inserted, updated, or removed.
+    pub(crate) fn refit(&mut self) {
+        if !self.is_dirty {
+            return;
+        }
+
+        let leaf_aabbs = &self.leaf_aabbs;
         self.qbvh
-            .clear_and_rebuild(DataGenerator(&self.colliders), 0.01);
+            .refit(QBVH_MARGIN, &mut self.workspace, |index| leaf_aabbs[index]);
+        self.qbvh.rebalance(QBVH_MARGIN, &mut self.workspace);
+        self.is_dirty = false;
     }
 
     pub(crate) fn entity_from_index(&self, index: u32) -> Entity {
         entity_from_index_and_gen(index, *self.entity_generations.get(&index).unwrap())
     }
 
+    /// Creates the [`RayHitData`] of a closest hit found by casting `ray` against the pipeline.
+    ///
+    /// Parry's composite shape visitors don't report the sub-shapes of the colliders,
+    /// so the ray is cast again against the hit collider if it consists of sub-shapes.
+    fn ray_hit_data(
+        &self,
+        entity_index: u32,
+        hit: RayIntersection,
+        ray: &Ray,
+        max_time_of_impact: Scalar,
+        solid: bool,
+    ) -> RayHitData {
+        let entity = self.entity_from_index(entity_index);
+        let subshape_index = self
+            .colliders
+            .get(&entity)
+            .filter(|(_, shape, _)| has_subshapes(&**shape))
+            .and_then(|(isometry, shape, _)| {
+                cast_ray_on_shape(&**shape, isometry, ray, max_time_of_impact, solid)
+            })
+            .and_then(|(_, subshape_index)| subshape_index);
+
+        RayHitData {
+            entity,
+            time_of_impact: hit.time_of_impact,
+            normal: hit.normal.into(),
+            subshape_index,
+        }
+    }
+
+    /// Creates the [`ShapeHitData`] of a closest hit found by casting `shape` against the pipeline.
+    ///
+    /// Parry's composite shape visitors don't report the sub-shapes of the colliders,
+    /// so the shape is cast again against the hit collider if it consists of sub-shapes.
+    fn shape_hit_data(
+        &self,
+        entity_index: u32,
+        hit: ShapeCastHit,
+        shape: &Collider,
+        shape_isometry: &Isometry<Scalar>,
+        shape_direction: &ParryVector<Scalar>,
+        options: ShapeCastOptions,
+    ) -> ShapeHitData {
+        let entity = self.entity_from_index(entity_index);
+        let subshape_index = self
+            .colliders
+            .get(&entity)
+            .filter(|(_, collider_shape, _)| has_subshapes(&**collider_shape))
+            .and_then(|(isometry, collider_shape, _)| {
+                cast_shape_on_shape(
+                    &*self.dispatcher,
+                    &**collider_shape,
+                    isometry,
+                    &**shape.shape_scaled(),
+                    shape_isometry,
+                    shape_direction,
+                    options,
+                )
+            })
+            .and_then(|(_, subshape_index)| subshape_index);
+
+        ShapeHitData {
+            entity,
+            time_of_impact: hit.time_of_impact,
+            point1: hit.witness1.into(),
+            point2: hit.witness2.into(),
+            normal1: hit.normal1.into(),
+            normal2: hit.normal2.into(),
+            subshape_index,
+        }
+    }
+
     /// Casts a [ray](spatial_query#raycasting) and computes the closest [hit](RayHitData) with a collider.
     /// If there are no hits, `None` is returned.
     ///
@@ -170,7 +353,7 @@ impl SpatialQueryPipeline {
         query_filter: SpatialQueryFilter,
     ) -> Option<RayHitData> {
         let pipeline_shape = self.as_composite_shape(query_filter);
-        let ray = parry::query::Ray::new(origin.into(), direction.adjust_precision().into());
+        let ray = Ray::new(origin.into(), direction.adjust_precision().into());
         let mut visitor = RayCompositeShapeToiAndNormalBestFirstVisitor::new(
             &pipeline_shape,
             &ray,
@@ -180,10 +363,8 @@ impl SpatialQueryPipeline {
 
         self.qbvh
             .traverse_best_first(&mut visitor)
-            .map(|(_, (entity_index, hit))| RayHitData {
-                entity: self.entity_from_index(entity_index),
-                time_of_impact: hit.time_of_impact,
-                normal: hit.normal.into(),
+            .map(|(_, (entity_index, hit))| {
+                self.ray_hit_data(entity_index, hit, &ray, max_time_of_impact, solid)
             })
     }
 
@@ -212,7 +393,7 @@ impl SpatialQueryPipeline {
         predicate: &dyn Fn(Entity) -> bool,
     ) -> Option<RayHitData> {
         let pipeline_shape = self.as_composite_shape_with_predicate(query_filter, predicate);
-        let ray = parry::query::Ray::new(origin.into(), direction.adjust_precision().into());
+        let ray = Ray::new(origin.into(), direction.adjust_precision().into());
         let mut visitor = RayCompositeShapeToiAndNormalBestFirstVisitor::new(
             &pipeline_shape,
             &ray,
@@ -222,10 +403,8 @@ impl SpatialQueryPipeline {
 
         self.qbvh
             .traverse_best_first(&mut visitor)
-            .map(|(_, (entity_index, hit))| RayHitData {
-                entity: self.entity_from_index(entity_index),
-                time_of_impact: hit.time_of_impact,
-                normal: hit.normal.into(),
+            .map(|(_, (entity_index, hit))| {
+                self.ray_hit_data(entity_index, hit, &ray, max_time_of_impact, solid)
             })
     }
 
@@ -296,22 +475,20 @@ impl SpatialQueryPipeline {
     ) {
         let colliders = &self.colliders;
 
-        let ray = parry::query::Ray::new(origin.into(), direction.adjust_precision().into());
+        let ray = Ray::new(origin.into(), direction.adjust_precision().into());
 
         let mut leaf_callback = &mut |entity_index: &u32| {
             let entity = self.entity_from_index(*entity_index);
             if let Some((iso, shape, layers)) = colliders.get(&entity) {
                 if query_filter.test(entity, *layers) {
-                    if let Some(hit) = shape.shape_scaled().cast_ray_and_get_normal(
-                        iso,
-                        &ray,
-                        max_time_of_impact,
-                        solid,
-                    ) {
+                    if let Some((hit, subshape_index)) =
+                        cast_ray_on_shape(&**shape, iso, &ray, max_time_of_impact, solid)
+                    {
                         let hit = RayHitData {
                             entity,
                             time_of_impact: hit.time_of_impact,
                             normal: hit.normal.into(),
+                            subshape_index,
                         };
 
                         return callback(hit);
@@ -367,6 +544,12 @@ impl SpatialQueryPipeline {
 
         let shape_isometry = make_isometry(origin, rotation);
         let shape_direction = direction.adjust_precision().into();
+        let options = ShapeCastOptions {
+            max_time_of_impact,
+            stop_at_penetration: !ignore_origin_penetration,
+            ..default()
+        };
+
         let pipeline_shape = self.as_composite_shape(query_filter);
         let mut visitor = TOICompositeShapeShapeBestFirstVisitor::new(
             &*self.dispatcher,
@@ -374,22 +557,20 @@ impl SpatialQueryPipeline {
             &shape_direction,
             &pipeline_shape,
             &**shape.shape_scaled(),
-            ShapeCastOptions {
-                max_time_of_impact,
-                stop_at_penetration: !ignore_origin_penetration,
-                ..default()
-            },
+            options,
         );
 
         self.qbvh
             .traverse_best_first(&mut visitor)
-            .map(|(_, (entity_index, hit))| ShapeHitData {
-                entity: self.entity_from_index(entity_index),
-                time_of_impact: hit.time_of_impact,
-                point1: hit.witness1.into(),
-                point2: hit.witness2.into(),
-                normal1: hit.normal1.into(),
-                normal2: hit.normal2.into(),
+            .map(|(_, (entity_index, hit))| {
+                self.shape_hit_data(
+                    entity_index,
+                    hit,
+                    shape,
+                    &shape_isometry,
+                    &shape_direction,
+                    options,
+                )
             })
     }
 
@@ -482,6 +663,11 @@ impl SpatialQueryPipeline {
 
         let shape_isometry = make_isometry(origin, rotation);
         let shape_direction = direction.adjust_precision().into();
+        let options = ShapeCastOptions {
+            max_time_of_impact,
+            stop_at_penetration: !ignore_origin_penetration,
+            ..default()
+        };
 
         loop {
             let pipeline_shape = self.as_composite_shape(query_filter.clone());
@@ -491,31 +677,23 @@ impl SpatialQueryPipeline {
                 &shape_direction,
                 &pipeline_shape,
                 &**shape.shape_scaled(),
-                ShapeCastOptions {
-                    max_time_of_impact,
-                    stop_at_penetration: !ignore_origin_penetration,
-                    ..default()
-                },
+                options,
             );
 
-            if let Some(hit) =
-                self.qbvh
-                    .traverse_best_first(&mut visitor)
-                    .map(|(_, (entity_index, hit))| ShapeHitData {
-                        entity: self.entity_from_index(entity_index),
-                        time_of_impact: hit.time_of_impact,
-                        point1: hit.witness1.into(),
-                        point2: hit.witness2.into(),
-                        normal1: hit.normal1.into(),
-                        normal2: hit.normal2.into(),
-                    })
-            {
-                query_filter.excluded_entities.insert(hit.entity);
-
-                if !callback(hit) {
-                    break;
-                }
-            } else {
+            let Some((_, (entity_index, hit))) = self.qbvh.traverse_best_first(&mut visitor) else {
+                break;
+            };
+            let hit = self.shape_hit_data(
+                entity_index,
+                hit,
+                shape,
+                &shape_isometry,
+                &shape_direction,
+                options,
+            );
+            query_filter.excluded_entities.insert(hit.entity);
+
+            if !callback(hit) {
                 break;
             }
         }
@@ -596,9 +774,7 @@ impl SpatialQueryPipeline {
         let mut leaf_callback = &mut |entity_index: &u32| {
             let entity = self.entity_from_index(*entity_index);
             if let Some((isometry, shape, layers)) = self.colliders.get(&entity) {
-                if query_filter.test(entity, *layers)
-                    && shape.shape_scaled().contains_point(isometry, &point)
-                {
+                if query_filter.test(entity, *layers) && shape.contains_point(isometry, &point) {
                     return callback(entity);
                 }
             }
@@ -719,14 +895,14 @@ impl SpatialQueryPipeline {
         let mut leaf_callback = &mut |entity_index: &u32| {
             let entity = self.entity_from_index(*entity_index);
 
-            if let Some((collider_isometry, collider, layers)) = colliders.get(&entity) {
+            if let Some((collider_isometry, collider_shape, layers)) = colliders.get(&entity) {
                 if query_filter.test(entity, *layers) {
                     let isometry = inverse_shape_isometry * collider_isometry;
 
                     if dispatcher.intersection_test(
                         &isometry,
                         &**shape.shape_scaled(),
-                        &**collider.shape_scaled(),
+                        &**collider_shape,
                     ) == Ok(true)
                     {
                         return callback(entity);
@@ -743,7 +919,7 @@ impl SpatialQueryPipeline {
 }
 
 pub(crate) struct QueryPipelineAsCompositeShape<'a> {
-    colliders: &'a HashMap<Entity, (Isometry<Scalar>, Collider, CollisionLayers)>,
+    colliders: &'a HashMap<Entity, (Isometry<Scalar>, SharedShape, CollisionLayers)>,
     pipeline: &'a SpatialQueryPipeline,
     query_filter: SpatialQueryFilter,
 }
@@ -769,7 +945,7 @@ impl<'a> TypedSimdCompositeShape for QueryPipelineAsCompositeShape<'a> {
             ))
         {
             if self.query_filter.test(*entity, *layers) {
-                f(Some(iso), &**shape.shape_scaled(), None);
+                f(Some(iso), &**shape, None);
             }
         }
     }
@@ -788,7 +964,7 @@ impl<'a> TypedSimdCompositeShape for QueryPipelineAsCompositeShape<'a> {
 }
 
 pub(crate) struct QueryPipelineAsCompositeShapeWithPredicate<'a, 'b> {
-    colliders: &'a HashMap<Entity, (Isometry<Scalar>, Collider, CollisionLayers)>,
+    colliders: &'a HashMap<Entity, (Isometry<Scalar>, SharedShape, CollisionLayers)>,
     pipeline: &'a SpatialQueryPipeline,
     query_filter: SpatialQueryFilter,
     predicate: &'b dyn Fn(Entity) -> bool,
@@ -815,7 +991,7 @@ impl<'a, 'b> TypedSimdCompositeShape for QueryPipelineAsCompositeShapeWithPredic
             ))
         {
             if self.query_filter.test(*entity, *layers) && (self.predicate)(*entity) {
-                f(Some(iso), &**shape.shape_scaled(), None);
+                f(Some(iso), &**shape, None);
             }
         }
     }
@@ -833,6 +1009,166 @@ impl<'a, 'b> TypedSimdCompositeShape for QueryPipelineAsCompositeShapeWithPredic
     }
 }
 
+/// Returns true if `shape` consists of sub-shapes whose indices are reported for hits.
+fn has_subshapes(shape: &dyn Shape) -> bool {
+    matches!(
+        shape.as_typed_shape(),
+        TypedShape::Compound(_)
+            | TypedShape::TriMesh(_)
+            | TypedShape::Polyline(_)
+            | TypedShape::HeightField(_)
+    )
+}
+
+/// Casts a ray against `shape` transformed by `isometry`.
+///
+/// Returns the hit and the index of the hit sub-shape reported by parry for composite shapes.
+/// See [`Collider::subshape_index_at_point`].
+fn cast_ray_on_shape(
+    shape: &dyn Shape,
+    isometry: &Isometry<Scalar>,
+    ray: &Ray,
+    max_time_of_impact: Scalar,
+    solid: bool,
+) -> Option<(RayIntersection, Option<u32>)> {
+    let local_ray = ray.inverse_transform_by(isometry);
+
+    let (hit, subshape_index) = match shape.as_typed_shape() {
+        TypedShape::Compound(compound) => {
+            cast_ray_on_composite_shape(compound, &local_ray, max_time_of_impact, solid)?
+        }
+        TypedShape::TriMesh(trimesh) => {
+            cast_ray_on_composite_shape(trimesh, &local_ray, max_time_of_impact, solid)?
+        }
+        TypedShape::Polyline(polyline) => {
+            cast_ray_on_composite_shape(polyline, &local_ray, max_time_of_impact, solid)?
+        }
+        TypedShape::HeightField(heightfield) => {
+            let hit =
+                heightfield.cast_local_ray_and_get_normal(&local_ray, max_time_of_impact, solid)?;
+            let subshape_index =
+                heightfield_subshape_index(heightfield, hit.feature).or_else(|| {
+                    // The ray hit an edge or a vertex shared by several elements.
+                    let point = local_ray.point_at(hit.time_of_impact);
+                    subshape_index_at_local_point(shape, point.into())
+                });
+            (hit, subshape_index)
+        }
+        _ => (
+            shape.cast_local_ray_and_get_normal(&local_ray, max_time_of_impact, solid)?,
+            None,
+        ),
+    };
+
+    Some((hit.transform_by(isometry), subshape_index))
+}
+
+/// Casts a ray against the parts of a composite shape in its local space,
+/// and returns the closest hit along with the ID of the hit part.
+fn cast_ray_on_composite_shape<S: TypedSimdCompositeShape<PartId = u32>>(
+    shape: &S,
+    local_ray: &Ray,
+    max_time_of_impact: Scalar,
+    solid: bool,
+) -> Option<(RayIntersection, Option<u32>)> {
+    let mut visitor = RayCompositeShapeToiAndNormalBestFirstVisitor::new(
+        shape,
+        local_ray,
+        max_time_of_impact,
+        solid,
+    );
+    shape
+        .typed_qbvh()
+        .traverse_best_first(&mut visitor)
+        .map(|(_, (part_id, hit))| (hit, Some(part_id)))
+}
+
+/// Returns the index of the element of a heightfield identified by a face `feature` of a ray hit.
+fn heightfield_subshape_index(heightfield: &HeightField, feature: FeatureId) -> Option<u32> {
+    #[cfg(feature = "2d")]
+    let element_count = heightfield.num_cells() as u32;
+    #[cfg(feature = "3d")]
+    let element_count = {
+        let (rows, columns) = heightfield.num_cells_ij();
+        2 * (rows * columns) as u32
+    };
+
+    // Back faces are identified by face IDs offset by the number of elements.
+    match feature {
+        FeatureId::Face(id) => Some(id % element_count),
+        _ => None,
+    }
+}
+
+/// Casts `shape2` transformed by `isometry2` in the given `direction` against `shape1`
+/// transformed by `isometry1`.
+///
+/// Returns the hit and the index of the hit sub-shape of `shape1` reported by parry for composite shapes.
+/// The witness point and normal on `shape1` are expressed in world space, like for
+/// [`SpatialQueryPipeline::cast_shape`].
+fn cast_shape_on_shape(
+    dispatcher: &dyn QueryDispatcher,
+    shape1: &dyn Shape,
+    isometry1: &Isometry<Scalar>,
+    shape2: &dyn Shape,
+    isometry2: &Isometry<Scalar>,
+    direction: &ParryVector<Scalar>,
+    options: ShapeCastOptions,
+) -> Option<(ShapeCastHit, Option<u32>)> {
+    let pos12 = isometry1.inv_mul(isometry2);
+    let vel12 = isometry1.inverse_transform_vector(direction);
+
+    let (hit, subshape_index) = match shape1.as_typed_shape() {
+        TypedShape::Compound(compound) => {
+            cast_shape_on_composite_shape(dispatcher, compound, &pos12, &vel12, shape2, options)?
+        }
+        TypedShape::TriMesh(trimesh) => {
+            cast_shape_on_composite_shape(dispatcher, trimesh, &pos12, &vel12, shape2, options)?
+        }
+        TypedShape::Polyline(polyline) => {
+            cast_shape_on_composite_shape(dispatcher, polyline, &pos12, &vel12, shape2, options)?
+        }
+        TypedShape::HeightField(_) => {
+            // Parry doesn't report the hit element of heightfields for shape casts,
+            // so find the element at the witness point in the local space of the heightfield.
+            let hit = dispatcher
+                .cast_shapes(&pos12, &vel12, shape1, shape2, options)
+                .ok()??;
+            (
+                hit,
+                subshape_index_at_local_point(shape1, hit.witness1.into()),
+            )
+        }
+        _ => (
+            dispatcher
+                .cast_shapes(&pos12, &vel12, shape1, shape2, options)
+                .ok()??,
+            None,
+        ),
+    };
+
+    Some((hit.transform1_by(isometry1), subshape_index))
+}
+
+/// Casts a shape against the parts of a composite shape in its local space,
+/// and returns the closest hit along with the ID of the hit part.
+fn cast_shape_on_composite_shape<S: TypedSimdCompositeShape<PartId = u32>>(
+    dispatcher: &dyn QueryDispatcher,
+    shape1: &S,
+    pos12: &Isometry<Scalar>,
+    vel12: &ParryVector<Scalar>,
+    shape2: &dyn Shape,
+    options: ShapeCastOptions,
+) -> Option<(ShapeCastHit, Option<u32>)> {
+    let mut visitor = TOICompositeShapeShapeBestFirstVisitor::new(
+        dispatcher, pos12, vel12, shape1, shape2, options,
+    );
+    shape1
+        .typed_qbvh()
+        .traverse_best_first(&mut visitor)
+        .map(|(_, (part_id, hit))| (hit, Some(part_id)))
+}
+
 fn entity_from_index_and_gen(index: u32, generation: u32) -> bevy::prelude::Entity {
     bevy::prelude::Entity::from_bits((generation as u64) << 32 | index as u64)
 }
@@ -850,3 +1186,253 @@ pub struct PointProjection {
     /// True if the point was inside of the collider.
     pub is_inside: bool,
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::tests::{ball_collider, box_collider, create_app, spawn_box, tick_60_fps};
+
+    #[test]
+    fn hits_and_contacts_report_subshape_index() {
+        let mut app = create_app();
+
+        app.insert_resource(Gravity::ZERO);
+        app.finish();
+
+        let part = box_collider(2.0, 1.0);
+
+        // A floor made of two boxes, and a box overlapping the second one.
+        let floor = app
+            .world_mut()
+            .spawn((
+                RigidBody::Static,
+                Collider::compound(vec![
+                    (Position(Vector::NEG_X), Rotation::default(), part.clone()),
+                    (Position(Vector::X), Rotation::default(), part),
+                ]),
+            ))
+            .id();
+        let body = spawn_box(&mut app, Vector::X + Vector::Y * 0.9);
+
+        tick_60_fps(&mut app);
+
+        let contacts = app
+            .world()
+            .resource::<Collisions>()
+            .get(floor, body)
+            .unwrap();
+        let (floor_index, body_index) = if contacts.entity1 == floor {
+            (
+                contacts.manifolds[0].subshape_index1,
+                contacts.manifolds[0].subshape_index2,
+            )
+        } else {
+            (
+                contacts.manifolds[0].subshape_index2,
+                contacts.manifolds[0].subshape_index1,
+            )
+        };
+        assert_eq!(floor_index, Some(1));
+        assert_eq!(body_index, None);
+
+        let pipeline = app.world().resource::<SpatialQueryPipeline>();
+        let filter = SpatialQueryFilter::from_excluded_entities([body]);
+
+        let ray_hit = pipeline
+            .cast_ray(
+                Vector::NEG_X + Vector::Y * 5.0,
+                Dir::NEG_Y,
+                10.0,
+                true,
+                filter.clone(),
+            )
+            .unwrap();
+        assert_eq!(ray_hit.entity, floor);
+        assert_eq!(ray_hit.subshape_index, Some(0));
+
+        let shape = ball_collider(0.25);
+        let shape_hit = pipeline
+            .cast_shape(
+                &shape,
+                Vector::X * 1.5 + Vector::Y * 5.0,
+                default(),
+                Dir::NEG_Y,
+                10.0,
+                true,
+                filter,
+            )
+            .unwrap();
+        assert_eq!(shape_hit.entity, floor);
+        assert_eq!(shape_hit.subshape_index, Some(1));
+
+        // Convex shapes don't have sub-shapes.
+        assert_eq!(
+            shape.subshape_index_at_point(Vector::ZERO, Rotation::default(), Vector::ZERO),
+            None
+        );
+
+        #[cfg(feature = "2d")]
+        {
+            let heightfield = Collider::heightfield(vec![0.0, 0.0, 0.0], Vector::new(2.0, 1.0));
+            let index = |x: Scalar| {
+                heightfield.subshape_index_at_point(
+                    Vector::ZERO,
+                    Rotation::default(),
+                    Vector::X * x,
+                )
+            };
+            assert_eq!(index(-0.5), Some(0));
+            assert_eq!(index(0.5), Some(1));
+        }
+        #[cfg(feature = "3d")]
+        {
+            let heightfield =
+                Collider::heightfield(vec![vec![0.0; 3]; 3], Vector::new(2.0, 1.0, 2.0));
+            let index = |point: Vector| {
+                heightfield.subshape_index_at_point(Vector::ZERO, Rotation::default(), point)
+            };
+            assert!(index(Vector::new(-0.5, 0.0, -0.5)).is_some());
+            assert_ne!(
+                index(Vector::new(-0.5, 0.0, -0.5)),
+                index(Vector::new(0.5, 0.0, 0.5))
+            );
+        }
+    }
+
+    #[test]
+    fn pipeline_reuses_collider_aabbs_and_removes_despawned_colliders() {
+        let mut app = create_app();
+
+        app.insert_resource(Gravity::ZERO);
+        app.finish();
+
+        let collider = box_collider(1.0, 1.0);
+
+        let entity = app
+            .world_mut()
+            .spawn((RigidBody::Static, Position(Vector::X * 5.0), collider))
+            .id();
+
+        tick_60_fps(&mut app);
+
+        // The leaf bounds of colliders that haven't moved since the broad phase are its AABBs.
+        let aabb = *app.world().get::<ColliderAabb>(entity).unwrap();
+        let pipeline = app.world().resource::<SpatialQueryPipeline>();
+        let leaf_aabb = pipeline.leaf_aabbs[&entity.index()];
+        assert_eq!(Vector::from(leaf_aabb.mins), aabb.min);
+        assert_eq!(Vector::from(leaf_aabb.maxs), aabb.max);
+        assert!(pipeline
+            .cast_ray(Vector::ZERO, Dir::X, 10.0, true, default())
+            .is_some());
+
+        // Despawned colliders are removed without waiting for the next pipeline update.
+        app.world_mut().despawn(entity);
+
+        let pipeline = app.world().resource::<SpatialQueryPipeline>();
+        assert!(!pipeline.colliders.contains_key(&entity));
+        assert!(pipeline
+            .cast_ray(Vector::ZERO, Dir::X, 10.0, true, default())
+            .is_none());
+    }
+
+    #[test]
+    fn pipeline_resets_removed_collision_layers() {
+        let mut app = create_app();
+
+        app.insert_resource(Gravity::ZERO);
+        app.finish();
+
+        let entity = app
+            .world_mut()
+            .spawn((
+                RigidBody::Static,
+                Position(Vector::X * 5.0),
+                box_collider(1.0, 1.0),
+                CollisionLayers::new(LayerMask(1 << 1), LayerMask::ALL),
+            ))
+            .id();
+
+        tick_60_fps(&mut app);
+
+        let filter = SpatialQueryFilter::from_mask(LayerMask(1));
+        let pipeline = app.world().resource::<SpatialQueryPipeline>();
+        assert!(pipeline
+            .cast_ray(Vector::ZERO, Dir::X, 10.0, true, filter.clone())
+            .is_none());
+
+        // Without layers, the collider belongs to the default layer again.
+        app.world_mut()
+            .entity_mut(entity)
+            .remove::<CollisionLayers>();
+
+        tick_60_fps(&mut app);
+
+        let pipeline = app.world().resource::<SpatialQueryPipeline>();
+        assert!(pipeline
+            .cast_ray(Vector::ZERO, Dir::X, 10.0, true, filter)
+            .is_some());
+    }
+
+    #[test]
+    fn hits_report_subshape_index_of_transformed_colliders() {
+        let mut app = create_app();
+
+        app.insert_resource(Gravity::ZERO);
+        app.finish();
+
+        let part = box_collider(2.0, 1.0);
+
+        // A compound made of two boxes, rotated by 90 degrees so that
+        // the first box is below the second one.
+        #[cfg(feature = "2d")]
+        let rotation = Rotation::degrees(90.0);
+        #[cfg(feature = "3d")]
+        let rotation = Rotation(Quaternion::from_rotation_z(PI / 2.0));
+        let compound = app
+            .world_mut()
+            .spawn((
+                RigidBody::Static,
+                Position(Vector::X * 10.0 + Vector::Y * 3.0),
+                rotation,
+                Collider::compound(vec![
+                    (Position(Vector::NEG_X), Rotation::default(), part.clone()),
+                    (Position(Vector::X), Rotation::default(), part),
+                ]),
+            ))
+            .id();
+
+        tick_60_fps(&mut app);
+
+        let pipeline = app.world().resource::<SpatialQueryPipeline>();
+        let filter = SpatialQueryFilter::default();
+
+        for (height, subshape_index) in [(2.0, 0), (4.0, 1)] {
+            let origin = Vector::X * 5.0 + Vector::Y * height;
+
+            let ray_hit = pipeline
+                .cast_ray(origin, Dir::X, 10.0, true, filter.clone())
+                .unwrap();
+            assert_eq!(ray_hit.entity, compound);
+            assert_eq!(ray_hit.subshape_index, Some(subshape_index));
+
+            let ray_hits = pipeline.ray_hits(origin, Dir::X, 10.0, 10, true, filter.clone());
+            assert_eq!(ray_hits.len(), 1);
+            assert_eq!(ray_hits[0].subshape_index, Some(subshape_index));
+
+            let shape = ball_collider(0.25);
+            let shape_hit = pipeline
+                .cast_shape(
+                    &shape,
+                    origin,
+                    default(),
+                    Dir::X,
+                    10.0,
+                    true,
+                    filter.clone(),
+                )
+                .unwrap();
+            assert_eq!(shape_hit.entity, compound);
+            assert_eq!(shape_hit.subshape_index, Some(subshape_index));
+        }
+    }
+}
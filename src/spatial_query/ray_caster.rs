@@ -3,13 +3,6 @@ use bevy::{
     ecs::entity::{EntityMapper, MapEntities},
     prelude::*,
 };
-#[cfg(all(
-    feature = "default-collider",
-    any(feature = "parry-f32", feature = "parry-f64")
-))]
-use parry::query::{
-    details::RayCompositeShapeToiAndNormalBestFirstVisitor, visitors::RayIntersectionsVisitor,
-};
 
 /// A component used for [raycasting](spatial_query#raycasting).
 ///
@@ -246,24 +239,12 @@ impl RayCaster {
         hits.count = 0;
 
         if self.max_hits == 1 {
-            let pipeline_shape = query_pipeline.as_composite_shape(query_filter);
-            let ray = parry::query::Ray::new(
-                self.global_origin().into(),
-                self.global_direction().adjust_precision().into(),
-            );
-            let mut visitor = RayCompositeShapeToiAndNormalBestFirstVisitor::new(
-                &pipeline_shape,
-                &ray,
+            if let Some(hit) = query_pipeline.cast_ray(
+                self.global_origin(),
+                self.global_direction(),
                 self.max_time_of_impact,
                 self.solid,
-            );
-
-            if let Some(hit) = query_pipeline.qbvh.traverse_best_first(&mut visitor).map(
-                |(_, (entity_index, hit))| RayHitData {
-                    entity: query_pipeline.entity_from_index(entity_index),
-                    time_of_impact: hit.time_of_impact,
-                    normal: hit.normal.into(),
-                },
+                query_filter,
             ) {
                 if (hits.vector.len() as u32) < hits.count + 1 {
                     hits.vector.push(hit);
@@ -273,47 +254,24 @@ impl RayCaster {
                 hits.count = 1;
             }
         } else {
-            let ray = parry::query::Ray::new(
-                self.global_origin().into(),
-                self.global_direction().adjust_precision().into(),
-            );
-
-            let mut leaf_callback = &mut |entity_index: &u32| {
-                let entity = query_pipeline.entity_from_index(*entity_index);
-                if let Some((iso, shape, layers)) = query_pipeline.colliders.get(&entity) {
-                    if query_filter.test(entity, *layers) {
-                        if let Some(hit) = shape.shape_scaled().cast_ray_and_get_normal(
-                            iso,
-                            &ray,
-                            self.max_time_of_impact,
-                            self.solid,
-                        ) {
-                            if (hits.vector.len() as u32) < hits.count + 1 {
-                                hits.vector.push(RayHitData {
-                                    entity,
-                                    time_of_impact: hit.time_of_impact,
-                                    normal: hit.normal.into(),
-                                });
-                            } else {
-                                hits.vector[hits.count as usize] = RayHitData {
-                                    entity,
-                                    time_of_impact: hit.time_of_impact,
-                                    normal: hit.normal.into(),
-                                };
-                            }
-
-                            hits.count += 1;
-
-                            return hits.count < self.max_hits;
-                        }
+            query_pipeline.ray_hits_callback(
+                self.global_origin(),
+                self.global_direction(),
+                self.max_time_of_impact,
+                self.solid,
+                query_filter,
+                |hit| {
+                    if (hits.vector.len() as u32) < hits.count + 1 {
+                        hits.vector.push(hit);
+                    } else {
+                        hits.vector[hits.count as usize] = hit;
                     }
-                }
-                true
-            };
 
-            let mut visitor =
-                RayIntersectionsVisitor::new(&ray, self.max_time_of_impact, &mut leaf_callback);
-            query_pipeline.qbvh.traverse_depth_first(&mut visitor);
+                    hits.count += 1;
+
+                    hits.count < self.max_hits
+                },
+            );
         }
     }
 }
@@ -424,6 +382,12 @@ pub struct RayHitData {
     pub time_of_impact: Scalar,
     /// The normal at the point of intersection.
     pub normal: Vector,
+    /// The index of the sub-shape of the collider that was hit, such as the child shape
+    /// of a compound collider or the triangle of a trimesh or heightfield collider.
+    ///
+    /// `None` for shapes that don't consist of sub-shapes.
+    /// See [`Collider::subshape_index_at_point`] for more details.
+    pub subshape_index: Option<u32>,
 }
 
 impl MapEntities for RayHitData {
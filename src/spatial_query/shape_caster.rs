@@ -3,7 +3,6 @@ use bevy::{
     ecs::entity::{EntityMapper, MapEntities},
     prelude::*,
 };
-use parry::query::{details::TOICompositeShapeShapeBestFirstVisitor, ShapeCastOptions};
 
 /// A component used for [shapecasting](spatial_query#shapecasting).
 ///
@@ -289,44 +288,19 @@ impl ShapeCaster {
 
         hits.count = 0;
 
-        let shape_rotation: Rotation;
-        #[cfg(feature = "2d")]
-        {
-            shape_rotation = Rotation::radians(self.global_shape_rotation());
-        }
-        #[cfg(feature = "3d")]
-        {
-            shape_rotation = Rotation::from(self.global_shape_rotation());
+        if self.max_hits == 0 {
+            return;
         }
 
-        let shape_isometry = make_isometry(self.global_origin(), shape_rotation);
-        let shape_direction = self.global_direction().adjust_precision().into();
-
-        while hits.count < self.max_hits {
-            let pipeline_shape = query_pipeline.as_composite_shape(query_filter.clone());
-            let mut visitor = TOICompositeShapeShapeBestFirstVisitor::new(
-                &*query_pipeline.dispatcher,
-                &shape_isometry,
-                &shape_direction,
-                &pipeline_shape,
-                &**self.shape.shape_scaled(),
-                ShapeCastOptions {
-                    max_time_of_impact: self.max_time_of_impact,
-                    stop_at_penetration: !self.ignore_origin_penetration,
-                    ..default()
-                },
-            );
-
-            if let Some(hit) = query_pipeline.qbvh.traverse_best_first(&mut visitor).map(
-                |(_, (entity_index, hit))| ShapeHitData {
-                    entity: query_pipeline.entity_from_index(entity_index),
-                    time_of_impact: hit.time_of_impact,
-                    point1: hit.witness1.into(),
-                    point2: hit.witness2.into(),
-                    normal1: hit.normal1.into(),
-                    normal2: hit.normal2.into(),
-                },
-            ) {
+        query_pipeline.shape_hits_callback(
+            &self.shape,
+            self.global_origin(),
+            self.global_shape_rotation(),
+            self.global_direction(),
+            self.max_time_of_impact,
+            self.ignore_origin_penetration,
+            query_filter,
+            |hit| {
                 if (hits.vector.len() as u32) < hits.count + 1 {
                     hits.vector.push(hit);
                 } else {
@@ -334,11 +308,10 @@ impl ShapeCaster {
                 }
 
                 hits.count += 1;
-                query_filter.excluded_entities.insert(hit.entity);
-            } else {
-                return;
-            }
-        }
+
+                hits.count < self.max_hits
+            },
+        );
     }
 }
 
@@ -436,6 +409,12 @@ pub struct ShapeHitData {
     /// The outward normal on the cast shape, at the time of impact,
     /// expressed in the local space of the cast shape.
     pub normal2: Vector,
+    /// The index of the sub-shape of the collider that was hit, such as the child shape
+    /// of a compound collider or the triangle of a trimesh or heightfield collider.
+    ///
+    /// `None` for shapes that don't consist of sub-shapes.
+    /// See [`Collider::subshape_index_at_point`] for more details.
+    pub subshape_index: Option<u32>,
 }
 
 impl MapEntities for ShapeHitData {
@@ -1,5 +1,17 @@
 use crate::prelude::*;
-use bevy::{ecs::system::SystemParam, prelude::*};
+use bevy::{
+    ecs::system::{SystemChangeTick, SystemParam},
+    prelude::*,
+};
+
+/// Colliders whose data in the [`SpatialQueryPipeline`] may be out of date.
+type ChangedColliderFilter = Or<(
+    Changed<Position>,
+    Changed<Rotation>,
+    Changed<Collider>,
+    Changed<CollisionLayers>,
+    Changed<ColliderAabb>,
+)>;
 
 /// A system parameter for performing [spatial queries](spatial_query).
 ///
@@ -61,18 +73,21 @@ use bevy::{ecs::system::SystemParam, prelude::*};
 /// ```
 #[derive(SystemParam)]
 pub struct SpatialQuery<'w, 's> {
-    pub(crate) colliders: Query<
+    pub(crate) changed_colliders: Query<
         'w,
         's,
         (
             Entity,
-            &'static Position,
-            &'static Rotation,
-            &'static Collider,
+            Ref<'static, Position>,
+            Ref<'static, Rotation>,
+            Ref<'static, Collider>,
             Option<&'static CollisionLayers>,
+            Option<Ref<'static, ColliderAabb>>,
         ),
+        ChangedColliderFilter,
     >,
-    pub(crate) added_colliders: Query<'w, 's, Entity, Added<Collider>>,
+    pub(crate) removed_layers: RemovedComponents<'w, 's, CollisionLayers>,
+    pub(crate) system_change_tick: SystemChangeTick,
     /// The [`SpatialQueryPipeline`].
     pub query_pipeline: ResMut<'w, SpatialQueryPipeline>,
 }
@@ -81,9 +96,45 @@ impl<'w, 's> SpatialQuery<'w, 's> {
     /// Updates the colliders in the pipeline. This is done automatically once per physics frame in
     /// [`PhysicsStepSet::SpatialQuery`], but if you modify colliders or their positions before that, you can
     /// call this to make sure the data is up to date when performing spatial queries using [`SpatialQuery`].
+    ///
+    /// The update is incremental: only colliders that have been added, moved, or otherwise changed
+    /// since the last time this system parameter was used are updated in the pipeline.
+    /// Removed colliders are removed from the pipeline immediately by the [`SpatialQueryPlugin`].
     pub fn update_pipeline(&mut self) {
-        self.query_pipeline
-            .update(self.colliders.iter(), self.added_colliders.iter());
+        let pipeline = &mut self.query_pipeline;
+        let this_run = self.system_change_tick.this_run();
+
+        // Colliders whose layers were removed use the default layers again.
+        // Colliders that got new layers since then are updated below.
+        for entity in self.removed_layers.read() {
+            if let Some((_, _, layers)) = pipeline.colliders.get_mut(&entity) {
+                *layers = CollisionLayers::default();
+            }
+        }
+
+        for (entity, position, rotation, collider, layers, aabb) in &self.changed_colliders {
+            // The broad phase AABB is only reused if the collider hasn't moved since it was computed.
+            let aabb = aabb.filter(|aabb| {
+                !position
+                    .last_changed()
+                    .is_newer_than(aabb.last_changed(), this_run)
+                    && !rotation
+                        .last_changed()
+                        .is_newer_than(aabb.last_changed(), this_run)
+            });
+
+            pipeline.insert_or_update_collider(
+                entity,
+                &position,
+                &rotation,
+                &collider,
+                collider.is_changed(),
+                layers.map_or(CollisionLayers::default(), |layers| *layers),
+                aabb.map(|aabb| *aabb),
+            );
+        }
+
+        pipeline.refit();
     }
 
     /// Casts a [ray](spatial_query#raycasting) and computes the closest [hit](RayHitData) with a collider.
@@ -5,7 +5,11 @@ use crate::prelude::*;
 ))]
 use approx::assert_relative_eq;
 use bevy::{
-    ecs::schedule::{LogLevel, ScheduleBuildSettings, ScheduleLabel},
+    app::PluginGroupBuilder,
+    ecs::{
+        schedule::{LogLevel, ScheduleBuildSettings, ScheduleLabel},
+        system::SystemParamItem,
+    },
     prelude::*,
     time::TimeUpdateStrategy,
     utils::Instant,
@@ -30,13 +34,17 @@ macro_rules! setup_insta {
     };
 }
 
-fn create_app() -> App {
+pub(crate) fn create_app() -> App {
+    create_app_with_physics(PhysicsPlugins::default().build())
+}
+
+pub(crate) fn create_app_with_physics(physics_plugins: PluginGroupBuilder) -> App {
     let mut app = App::new();
     app.add_plugins((
         MinimalPlugins,
         TransformPlugin,
         HierarchyPlugin,
-        PhysicsPlugins::default(),
+        physics_plugins,
         bevy::asset::AssetPlugin::default(),
         #[cfg(feature = "bevy_scene")]
         bevy::scene::ScenePlugin,
@@ -47,7 +55,22 @@ fn create_app() -> App {
     app
 }
 
-fn tick_60_fps(app: &mut App) {
+/// Creates an app that uses the given [`CollisionHooks`] in the narrow phase.
+pub(crate) fn create_app_with_hooks<H: CollisionHooks + 'static>() -> App
+where
+    for<'w, 's> SystemParamItem<'w, 's, H>: CollisionHooks,
+{
+    create_app_with_physics(
+        PhysicsPlugins::default()
+            .build()
+            .disable::<NarrowPhasePlugin<Collider>>()
+            .add_after::<NarrowPhasePlugin<Collider>, _>(
+                NarrowPhasePlugin::<Collider, H>::default(),
+            ),
+    )
+}
+
+pub(crate) fn tick_60_fps(app: &mut App) {
     let mut update_strategy = app.world_mut().resource_mut::<TimeUpdateStrategy>();
     let TimeUpdateStrategy::ManualInstant(prev_time) = *update_strategy else {
         unimplemented!()
@@ -57,6 +80,60 @@ fn tick_60_fps(app: &mut App) {
     app.update();
 }
 
+/// Runs the given number of frames at 60 FPS.
+pub(crate) fn tick_frames(app: &mut App, frames: usize) {
+    for _ in 0..frames {
+        tick_60_fps(app);
+    }
+}
+
+/// Returns a rectangle collider in 2D and a cuboid collider in 3D.
+/// In 3D, the depth is equal to the width.
+pub(crate) fn box_collider(width: Scalar, height: Scalar) -> Collider {
+    #[cfg(feature = "2d")]
+    {
+        Collider::rectangle(width, height)
+    }
+    #[cfg(feature = "3d")]
+    {
+        Collider::cuboid(width, height, width)
+    }
+}
+
+/// Returns a circle collider in 2D and a sphere collider in 3D.
+pub(crate) fn ball_collider(radius: Scalar) -> Collider {
+    #[cfg(feature = "2d")]
+    {
+        Collider::circle(radius)
+    }
+    #[cfg(feature = "3d")]
+    {
+        Collider::sphere(radius)
+    }
+}
+
+/// Spawns a static floor with the given width and a height of `1.0` at the given position.
+pub(crate) fn spawn_floor(app: &mut App, position: Vector, width: Scalar) -> Entity {
+    app.world_mut()
+        .spawn((
+            RigidBody::Static,
+            Position(position),
+            box_collider(width, 1.0),
+        ))
+        .id()
+}
+
+/// Spawns a dynamic body with a unit box collider at the given position.
+pub(crate) fn spawn_box(app: &mut App, position: Vector) -> Entity {
+    app.world_mut()
+        .spawn((
+            RigidBody::Dynamic,
+            Position(position),
+            box_collider(1.0, 1.0),
+        ))
+        .id()
+}
+
 #[cfg(all(feature = "3d", feature = "default-collider"))]
 fn setup_cubes_simulation(mut commands: Commands) {
     let mut next_id = 0;
@@ -248,6 +325,1111 @@ fn cubes_simulation_is_locally_deterministic() {
     }
 }
 
+#[cfg(all(
+    feature = "default-collider",
+    any(feature = "parry-f32", feature = "parry-f64")
+))]
+#[test]
+fn alternative_broad_phases_match_sweep_and_prune() {
+    fn collect_pairs(physics_plugins: PluginGroupBuilder) -> Vec<(Entity, Entity)> {
+        let mut app = create_app_with_physics(physics_plugins);
+
+        app.insert_resource(Gravity::ZERO);
+
+        app.add_systems(Startup, |mut commands: Commands| {
+            // A long, tightly packed row of bodies with a few stacked on top,
+            // along with a static floor that overlaps all of them.
+            // There are enough bodies for the sweep to be split into several parallel chunks.
+            // Some bodies are on a layer that doesn't interact with the floor.
+            commands.spawn((
+                RigidBody::Static,
+                Position(Vector::NEG_Y * 0.5),
+                #[cfg(feature = "2d")]
+                Collider::rectangle(1000.0, 1.0),
+                #[cfg(feature = "3d")]
+                Collider::cuboid(1000.0, 1.0, 2.0),
+            ));
+            for i in 0..400 {
+                for j in 0..3 {
+                    let layers = if i % 5 == 0 {
+                        CollisionLayers::from_bits(0b10, 0b10)
+                    } else {
+                        CollisionLayers::default()
+                    };
+                    commands.spawn((
+                        RigidBody::Dynamic,
+                        Position(Vector::X * i as Scalar * 0.9 + Vector::Y * j as Scalar * 0.9),
+                        ball_collider(0.5),
+                        layers,
+                    ));
+                }
+            }
+        });
+
+        tick_60_fps(&mut app);
+
+        let mut pairs = app.world().resource::<BroadCollisionPairs>().0.clone();
+        pairs.sort();
+        pairs
+    }
+
+    let sweep_and_prune_pairs = collect_pairs(PhysicsPlugins::default().build());
+    let tree_pairs = collect_pairs(
+        PhysicsPlugins::default()
+            .build()
+            .disable::<BroadPhasePlugin>()
+            .add(DynamicTreeBroadPhasePlugin),
+    );
+    let spatial_hash_pairs = collect_pairs(
+        PhysicsPlugins::default()
+            .build()
+            .disable::<BroadPhasePlugin>()
+            .add(SpatialHashBroadPhasePlugin),
+    );
+
+    assert!(!sweep_and_prune_pairs.is_empty());
+    assert_eq!(sweep_and_prune_pairs, tree_pairs);
+    assert_eq!(sweep_and_prune_pairs, spatial_hash_pairs);
+}
+
+#[cfg(all(
+    feature = "default-collider",
+    any(feature = "parry-f32", feature = "parry-f64")
+))]
+#[test]
+fn spatial_query_pipeline_tracks_collider_changes() {
+    fn spawn_collider(app: &mut App, position: Vector) -> Entity {
+        app.world_mut()
+            .spawn((RigidBody::Static, Position(position), ball_collider(0.5)))
+            .id()
+    }
+
+    fn hits(app: &App, point: Vector) -> Vec<Entity> {
+        app.world()
+            .resource::<SpatialQueryPipeline>()
+            .point_intersections(point, SpatialQueryFilter::default())
+    }
+
+    let mut app = create_app();
+
+    let a = spawn_collider(&mut app, Vector::ZERO);
+    let b = spawn_collider(&mut app, Vector::X * 5.0);
+    let c = spawn_collider(&mut app, Vector::X * 10.0);
+
+    tick_60_fps(&mut app);
+
+    assert_eq!(hits(&app, Vector::ZERO), vec![a]);
+    assert_eq!(hits(&app, Vector::X * 5.0), vec![b]);
+    assert_eq!(hits(&app, Vector::X * 10.0), vec![c]);
+
+    // Move one collider and despawn another.
+    app.world_mut().get_mut::<Position>(a).unwrap().0 = Vector::X * 20.0;
+    app.world_mut().despawn(b);
+
+    tick_60_fps(&mut app);
+
+    assert!(hits(&app, Vector::ZERO).is_empty());
+    assert_eq!(hits(&app, Vector::X * 20.0), vec![a]);
+    assert!(hits(&app, Vector::X * 5.0).is_empty());
+    assert_eq!(hits(&app, Vector::X * 10.0), vec![c]);
+
+    // Spawn a new collider, which may reuse the index of the despawned entity.
+    let d = spawn_collider(&mut app, Vector::X * 5.0);
+
+    tick_60_fps(&mut app);
+
+    assert_eq!(hits(&app, Vector::X * 5.0), vec![d]);
+    assert_eq!(
+        app.world()
+            .resource::<SpatialQueryPipeline>()
+            .colliders
+            .len(),
+        3
+    );
+}
+
+#[cfg(all(
+    feature = "default-collider",
+    any(feature = "parry-f32", feature = "parry-f64")
+))]
+#[test]
+fn subshape_materials_override_collider_materials() {
+    let mut app = create_app();
+
+    app.insert_resource(Gravity::ZERO);
+    app.finish();
+
+    let part = box_collider(2.0, 1.0);
+
+    // A floor made of two boxes, where only the second box has a material.
+    let ice = SubShapeMaterial::new(Friction::ZERO, Restitution::new(0.8));
+    let floor = app
+        .world_mut()
+        .spawn((
+            RigidBody::Static,
+            Collider::compound(vec![
+                (Position(Vector::NEG_X), Rotation::default(), part.clone()),
+                (Position(Vector::X), Rotation::default(), part),
+            ]),
+            Friction::new(1.0),
+            SubShapeMaterials::new(vec![ice], vec![u32::MAX, 0]),
+        ))
+        .id();
+
+    let mut spawn_body = |x: Scalar| {
+        app.world_mut()
+            .spawn((
+                RigidBody::Dynamic,
+                Position(Vector::X * x + Vector::Y * 0.9),
+                box_collider(0.5, 1.0),
+            ))
+            .id()
+    };
+    let body1 = spawn_body(-1.0);
+    let body2 = spawn_body(1.0);
+
+    tick_60_fps(&mut app);
+
+    let collisions = app.world().resource::<Collisions>();
+    let manifold = |body: Entity| collisions.get(floor, body).unwrap().manifolds[0].clone();
+
+    // The first body is touching the box without a material.
+    let manifold1 = manifold(body1);
+    assert_eq!(
+        manifold1.friction,
+        Friction::new(1.0).combine(Friction::default())
+    );
+    assert_eq!(manifold1.restitution, Restitution::default());
+
+    // The second body is touching the icy box.
+    let manifold2 = manifold(body2);
+    assert_eq!(
+        manifold2.friction,
+        Friction::ZERO.combine(Friction::default())
+    );
+    assert_eq!(
+        manifold2.restitution,
+        Restitution::new(0.8).combine(Restitution::default())
+    );
+}
+
+#[cfg(all(
+    feature = "2d",
+    feature = "default-collider",
+    any(feature = "parry-f32", feature = "parry-f64")
+))]
+#[test]
+fn chain_colliders_prevent_ghost_collisions() {
+    let mut app = create_app();
+
+    app.insert_resource(Gravity(Vector::NEG_Y * 9.81));
+    app.finish();
+
+    // Flat ground made of many short segments.
+    let vertices = (-20..=20)
+        .map(|x| Vector::new(x as Scalar * 0.5, 0.0))
+        .collect::<Vec<_>>();
+    let ground = app
+        .world_mut()
+        .spawn((
+            RigidBody::Static,
+            Collider::chain(vertices, false),
+            Friction::ZERO.with_combine_rule(CoefficientCombine::Min),
+        ))
+        .id();
+
+    // A box sliding across the ground.
+    let body = app
+        .world_mut()
+        .spawn((
+            RigidBody::Dynamic,
+            Position(Vector::new(-8.0, 0.5)),
+            LinearVelocity(Vector::X * 5.0),
+            Collider::rectangle(1.0, 1.0),
+            LockedAxes::ROTATION_LOCKED,
+        ))
+        .id();
+
+    for _ in 0..120 {
+        tick_60_fps(&mut app);
+
+        // Contacts at the internal vertices use the normal of the segments.
+        if let Some(contacts) = app.world().resource::<Collisions>().get(ground, body) {
+            for manifold in contacts.manifolds.iter() {
+                let normal = if contacts.entity1 == ground {
+                    manifold.normal1
+                } else {
+                    manifold.normal2
+                };
+                assert!(normal.y > 0.999, "unexpected contact normal {normal}");
+            }
+        }
+    }
+
+    // The box slides across the ground without snagging.
+    let velocity = app.world().get::<LinearVelocity>(body).unwrap();
+    assert!((velocity.x - 5.0).abs() < 0.01);
+    assert!(velocity.y.abs() < 0.01);
+}
+
+#[cfg(all(
+    feature = "default-collider",
+    any(feature = "parry-f32", feature = "parry-f64")
+))]
+#[test]
+fn coplanar_manifolds_are_merged_and_reduced() {
+    // Returns the contact manifolds between a box and flat ground made of many segments or triangles.
+    // If `icy_half` is `true`, the segments or triangles on the negative X side have an icy material.
+    let compute_manifolds = |reduce_manifolds: bool, icy_half: bool| {
+        let mut app = create_app();
+
+        app.insert_resource(Gravity::ZERO);
+        app.insert_resource(NarrowPhaseConfig {
+            reduce_manifolds,
+            ..default()
+        });
+        app.finish();
+
+        #[cfg(feature = "2d")]
+        let ground = Collider::polyline(
+            (-8..=8)
+                .map(|x| Vector::new(x as Scalar * 0.5, 0.0))
+                .collect(),
+            None,
+        );
+        #[cfg(feature = "3d")]
+        let ground = {
+            let vertices = (-8..=8)
+                .flat_map(|z| {
+                    (-8..=8).map(move |x| Vector::new(x as Scalar, 0.0, z as Scalar) * 0.5)
+                })
+                .collect();
+            let indices = (0..16)
+                .flat_map(|z| {
+                    (0..16).flat_map(move |x| {
+                        let i = z * 17 + x;
+                        [[i, i + 17, i + 1], [i + 1, i + 17, i + 18]]
+                    })
+                })
+                .collect();
+            Collider::trimesh(vertices, indices)
+        };
+
+        // The sub-shapes are ordered by their X coordinate within each row,
+        // with one segment or two triangles per cell and 16 cells per row.
+        #[cfg(feature = "2d")]
+        let (subshapes_per_cell, subshape_count) = (1, 16);
+        #[cfg(feature = "3d")]
+        let (subshapes_per_cell, subshape_count) = (2, 16 * 16 * 2);
+        let ice = SubShapeMaterial::new(Friction::ZERO, Restitution::ZERO);
+        let materials = SubShapeMaterials::new(
+            vec![ice],
+            (0..subshape_count)
+                .map(|i| {
+                    if icy_half && (i / subshapes_per_cell) % 16 < 8 {
+                        0
+                    } else {
+                        u32::MAX
+                    }
+                })
+                .collect(),
+        );
+
+        let ground = app
+            .world_mut()
+            .spawn((RigidBody::Static, ground, materials))
+            .id();
+        let body = app
+            .world_mut()
+            .spawn((
+                RigidBody::Dynamic,
+                Position(Vector::Y * 0.49),
+                box_collider(1.8, 1.0),
+            ))
+            .id();
+
+        tick_60_fps(&mut app);
+
+        let collisions = app.world().resource::<Collisions>();
+        collisions.get(ground, body).unwrap().manifolds.clone()
+    };
+
+    // Without reduction, each segment or triangle has its own manifold.
+    assert!(compute_manifolds(false, false).len() > 1);
+
+    // With reduction, the manifolds are merged into one manifold with a limited number of contacts.
+    let manifolds = compute_manifolds(true, false);
+    assert_eq!(manifolds.len(), 1);
+    assert_eq!(manifolds[0].index, 0);
+    #[cfg(feature = "2d")]
+    assert_eq!(manifolds[0].contacts.len(), 2);
+    #[cfg(feature = "3d")]
+    assert_eq!(manifolds[0].contacts.len(), 4);
+
+    // Manifolds with different materials are not merged, so the materials are preserved.
+    // The contact limit still applies to the whole pair.
+    let manifolds = compute_manifolds(true, true);
+    assert_eq!(manifolds.len(), 2);
+    assert_ne!(manifolds[0].friction, manifolds[1].friction);
+    assert_eq!(manifolds[1].index, 1);
+    let contact_count: usize = manifolds.iter().map(|m| m.contacts.len()).sum();
+    #[cfg(feature = "2d")]
+    assert_eq!(contact_count, 2);
+    #[cfg(feature = "3d")]
+    assert_eq!(contact_count, 4);
+}
+
+#[cfg(all(
+    feature = "default-collider",
+    any(feature = "parry-f32", feature = "parry-f64")
+))]
+#[test]
+fn sensors_only_compute_contacts_when_requested() {
+    let mut app = create_app();
+
+    app.insert_resource(Gravity::ZERO);
+    app.finish();
+
+    let mut spawn_sensor = |x: Scalar| {
+        app.world_mut()
+            .spawn((
+                RigidBody::Static,
+                Position(Vector::X * x),
+                ball_collider(1.0),
+                Sensor,
+            ))
+            .id()
+    };
+    let sensor = spawn_sensor(0.0);
+    let sensor_with_contacts = spawn_sensor(10.0);
+    app.world_mut()
+        .entity_mut(sensor_with_contacts)
+        .insert(SensorContacts);
+
+    let mut spawn_body = |x: Scalar| {
+        app.world_mut()
+            .spawn((
+                RigidBody::Dynamic,
+                Position(Vector::X * x),
+                ball_collider(0.5),
+            ))
+            .id()
+    };
+    let body1 = spawn_body(1.0);
+    let body2 = spawn_body(11.0);
+
+    tick_60_fps(&mut app);
+
+    let collisions = app.world().resource::<Collisions>();
+
+    // The sensor only tests for intersection, so the collision has no manifolds.
+    let contacts = collisions.get(sensor, body1).unwrap();
+    assert!(contacts.is_sensor);
+    assert!(contacts.manifolds.is_empty());
+
+    // The other sensor opted in to computing contacts.
+    let contacts = collisions.get(sensor_with_contacts, body2).unwrap();
+    assert!(contacts.is_sensor);
+    assert!(!contacts.manifolds.is_empty());
+
+    // Intersections still produce collision events.
+    let started = app.world().resource::<Events<CollisionStarted>>();
+    assert_eq!(started.get_reader().read(started).count(), 2);
+}
+
+#[cfg(all(
+    feature = "default-collider",
+    any(feature = "parry-f32", feature = "parry-f64")
+))]
+#[test]
+fn contact_cache_reuses_manifolds_for_small_motion() {
+    // Returns the contact point and penetration depth on a ball resting on a tilted ground
+    // before and after the ball is rotated by the given angle and moved through the given heights,
+    // one height per step. The contact after the motion is `None` if the ball is no longer touching the ground.
+    let compute_contact_points =
+        |contact_cache: Option<ContactCacheTolerance>, angle: Scalar, heights: &[Scalar]| {
+            let mut app = create_app();
+
+            app.insert_resource(Gravity::ZERO);
+            app.insert_resource(NarrowPhaseConfig {
+                contact_cache,
+                ..default()
+            });
+            app.finish();
+
+            // Tilt the ground so that its AABB still overlaps the ball
+            // when the ball is moved away from it along the contact normal.
+            #[cfg(feature = "2d")]
+            let ground_rotation = Rotation::degrees(45.0);
+            #[cfg(feature = "3d")]
+            let ground_rotation = Rotation(Quaternion::from_rotation_z(PI / 4.0));
+            let normal = ground_rotation * Vector::Y;
+
+            let ground = app
+                .world_mut()
+                .spawn((RigidBody::Static, ground_rotation, box_collider(10.0, 1.0)))
+                .id();
+            let ball = app
+                .world_mut()
+                .spawn((
+                    RigidBody::Kinematic,
+                    Position(normal * 0.999),
+                    ball_collider(0.5),
+                ))
+                .id();
+
+            let contact_point = |app: &App| {
+                let contacts = app.world().resource::<Collisions>().get(ground, ball)?;
+                let contact = contacts.manifolds.first()?.contacts.first()?;
+                let penetration = contact.penetration;
+                if contacts.entity1 == ball {
+                    Some((contact.point1, penetration))
+                } else {
+                    Some((contact.point2, penetration))
+                }
+            };
+
+            tick_60_fps(&mut app);
+            let before = contact_point(&app).unwrap();
+
+            // Rotate and move the ball.
+            #[cfg(feature = "2d")]
+            let rotation = Rotation::radians(angle);
+            #[cfg(feature = "3d")]
+            let rotation = Rotation(Quaternion::from_rotation_z(angle));
+            *app.world_mut().get_mut::<Rotation>(ball).unwrap() = rotation;
+            for &height in heights {
+                app.world_mut().get_mut::<Position>(ball).unwrap().0 = normal * height;
+                tick_60_fps(&mut app);
+            }
+            let after = contact_point(&app);
+
+            (before, after)
+        };
+
+    // Without the cache, the contact point on the ball is recomputed.
+    let (before, after) = compute_contact_points(None, 0.002, &[0.9995]);
+    assert_ne!(before.0, after.unwrap().0);
+
+    // With the cache, the contacts are reused, but the penetration depth is updated.
+    let (before, after) =
+        compute_contact_points(Some(ContactCacheTolerance::default()), 0.002, &[0.9995]);
+    let after = after.unwrap();
+    assert_eq!(before.0, after.0);
+    assert!((before.1 - 0.001).abs() < 1e-4);
+    assert!((after.1 - 0.0005).abs() < 1e-4);
+
+    // Larger rotations invalidate the cached contacts.
+    let (before, after) =
+        compute_contact_points(Some(ContactCacheTolerance::default()), 0.1, &[0.9995]);
+    assert_ne!(before.0, after.unwrap().0);
+
+    // Larger motion along the contact normal also invalidates the cached contacts.
+    let (before, after) =
+        compute_contact_points(Some(ContactCacheTolerance::default()), 0.002, &[0.99]);
+    assert_ne!(before.0, after.unwrap().0);
+
+    // Moving the ball away along the contact normal invalidates the cached contacts,
+    // so the contacts are recomputed and the collision ends even though the AABBs still overlap.
+    let (_, after) = compute_contact_points(Some(ContactCacheTolerance::default()), 0.0, &[1.008]);
+    assert!(after.is_none());
+
+    // Small steps along the normal are within the tolerance, but the cached contacts
+    // are still invalidated once the ball is farther away than the maximum contact distance.
+    let (_, after) =
+        compute_contact_points(Some(ContactCacheTolerance::default()), 0.0, &[1.003, 1.007]);
+    assert!(after.is_none());
+}
+
+#[cfg(all(
+    feature = "default-collider",
+    any(feature = "parry-f32", feature = "parry-f64")
+))]
+#[test]
+fn contact_cache_resets_contact_overrides() {
+    use bevy::ecs::system::SystemParam;
+
+    /// Overrides the friction of every contact point while `true`.
+    #[derive(Resource)]
+    struct OverrideFriction(bool);
+
+    #[derive(SystemParam)]
+    struct TestHooks<'w> {
+        override_friction: Res<'w, OverrideFriction>,
+    }
+
+    impl CollisionHooks for TestHooks<'_> {
+        fn modify_contacts(&self, contacts: &mut Contacts, _commands: &mut Commands) -> bool {
+            if self.override_friction.0 {
+                for contact in contacts
+                    .manifolds
+                    .iter_mut()
+                    .flat_map(|manifold| &mut manifold.contacts)
+                {
+                    contact.friction = Some(Friction::new(0.25));
+                }
+            }
+            true
+        }
+    }
+
+    let mut app = create_app_with_hooks::<TestHooks>();
+
+    app.insert_resource(Gravity::ZERO)
+        .insert_resource(OverrideFriction(true))
+        .insert_resource(NarrowPhaseConfig {
+            contact_cache: Some(ContactCacheTolerance::default()),
+            ..default()
+        });
+    app.finish();
+
+    // Neither body moves, so the cached contacts are reused on every step.
+    let ground = spawn_floor(&mut app, Vector::ZERO, 10.0);
+    let ball = app
+        .world_mut()
+        .spawn((
+            RigidBody::Kinematic,
+            Position(Vector::Y * 0.999),
+            ball_collider(0.5),
+        ))
+        .id();
+
+    let contact_friction = |app: &App| {
+        let contacts = app.world().resource::<Collisions>().get(ground, ball);
+        contacts.unwrap().manifolds[0].contacts[0].friction
+    };
+
+    tick_frames(&mut app, 2);
+    assert_eq!(contact_friction(&app), Some(Friction::new(0.25)));
+
+    // Once the hook stops overriding the friction, the override is no longer used.
+    app.world_mut().resource_mut::<OverrideFriction>().0 = false;
+    tick_60_fps(&mut app);
+    assert_eq!(contact_friction(&app), None);
+}
+
+#[cfg(all(
+    feature = "serialize",
+    feature = "default-collider",
+    any(feature = "parry-f32", feature = "parry-f64")
+))]
+#[test]
+fn colliders_round_trip_through_serde_and_reflection() {
+    use bevy::reflect::{
+        serde::{ReflectDeserializer, ReflectSerializer},
+        TypeRegistry,
+    };
+    use serde::de::DeserializeSeed;
+
+    let mut scaled = Collider::capsule(0.5, 2.0);
+    scaled.set_scale(Vector::splat(2.0), 10);
+
+    let colliders = vec![
+        scaled,
+        Collider::compound(vec![
+            (
+                Position::default(),
+                Rotation::default(),
+                Collider::capsule(0.5, 1.0),
+            ),
+            (
+                Position(Vector::X),
+                Rotation::default(),
+                Collider::capsule(1.0, 0.5),
+            ),
+        ]),
+        Collider::polyline(vec![Vector::ZERO, Vector::X, Vector::ONE], None),
+        Collider::convex_hull(vec![Vector::ZERO, Vector::X, Vector::Y, Vector::ONE]).unwrap(),
+        #[cfg(feature = "2d")]
+        Collider::circle(0.5),
+        #[cfg(feature = "2d")]
+        Collider::rectangle(1.0, 2.0),
+        #[cfg(feature = "2d")]
+        Collider::chain(vec![Vector::ZERO, Vector::X, Vector::ONE], true),
+        #[cfg(feature = "2d")]
+        Collider::heightfield(vec![0.0, 1.0, 0.5, 2.0], Vector::new(4.0, 1.0)),
+        #[cfg(feature = "3d")]
+        Collider::sphere(0.5),
+        #[cfg(feature = "3d")]
+        Collider::cuboid(1.0, 2.0, 3.0),
+        #[cfg(feature = "3d")]
+        Collider::cylinder(0.5, 2.0),
+        #[cfg(feature = "3d")]
+        Collider::trimesh(
+            vec![Vector::ZERO, Vector::X, Vector::Z, Vector::ONE],
+            vec![[0, 1, 2], [1, 3, 2]],
+        ),
+        #[cfg(feature = "3d")]
+        Collider::heightfield(
+            vec![vec![0.0, 1.0], vec![0.5, 2.0]],
+            Vector::new(4.0, 1.0, 4.0),
+        ),
+    ];
+
+    let mut registry = TypeRegistry::default();
+    registry.register::<Collider>();
+
+    for collider in colliders {
+        let check = |deserialized: &Collider| {
+            assert_eq!(format!("{deserialized:?}"), format!("{collider:?}"));
+            assert_eq!(deserialized.scale(), collider.scale());
+            assert_eq!(
+                deserialized.aabb(Vector::ZERO, Rotation::default()),
+                collider.aabb(Vector::ZERO, Rotation::default())
+            );
+            #[cfg(feature = "2d")]
+            assert_eq!(deserialized.is_chain(), collider.is_chain());
+        };
+
+        // Serde
+        let serialized = ron::to_string(&collider).unwrap();
+        let deserialized: Collider = ron::from_str(&serialized).unwrap();
+        check(&deserialized);
+
+        // Reflection, as used by scenes
+        let serialized = ron::to_string(&ReflectSerializer::new(&collider, &registry)).unwrap();
+        let mut deserializer = ron::Deserializer::from_str(&serialized).unwrap();
+        let reflected = ReflectDeserializer::new(&registry)
+            .deserialize(&mut deserializer)
+            .unwrap();
+        check(&Collider::from_reflect(reflected.as_ref()).unwrap());
+    }
+}
+
+#[test]
+fn surface_velocity_carries_bodies() {
+    let mut app = create_app();
+
+    app.finish();
+
+    // A conveyor belt rotated by 180 degrees, so its surface moves along the negative X axis.
+    let floor = spawn_floor(&mut app, Vector::NEG_Y * 0.5, 100.0);
+    app.world_mut().entity_mut(floor).insert((
+        #[cfg(feature = "2d")]
+        Rotation::degrees(180.0),
+        #[cfg(feature = "3d")]
+        Rotation(Quaternion::from_rotation_z(PI)),
+        SurfaceVelocity(Vector::X * 2.0),
+    ));
+    let body = spawn_box(&mut app, Vector::Y * 0.5);
+
+    tick_frames(&mut app, 60);
+
+    let velocity = app.world().get::<LinearVelocity>(body).unwrap();
+    assert_relative_eq!(velocity.x, -2.0, epsilon = 0.1);
+}
+
+#[test]
+fn joints_disable_collisions_between_connected_bodies() {
+    let mut app = create_app();
+
+    app.insert_resource(Gravity::ZERO);
+
+    app.finish();
+
+    // Two pairs of overlapping bodies.
+    let body1 = spawn_box(&mut app, Vector::ZERO);
+    let body2 = spawn_box(&mut app, Vector::X * 0.5);
+    let body3 = spawn_box(&mut app, Vector::Y * 10.0);
+    let body4 = spawn_box(&mut app, Vector::Y * 10.0 + Vector::X * 0.5);
+
+    app.world_mut().spawn(RevoluteJoint::new(body1, body2));
+    app.world_mut()
+        .spawn(RevoluteJoint::new(body3, body4).with_collide_connected(true));
+
+    tick_60_fps(&mut app);
+
+    let collisions = app.world().resource::<Collisions>();
+    assert!(!collisions.contains(body1, body2));
+    assert!(collisions.contains(body3, body4));
+}
+
+#[test]
+fn joint_plugin_disables_collisions_for_custom_joints() {
+    use crate::dynamics::solver::xpbd::{AngularConstraint, PositionConstraint, XpbdConstraint};
+    use bevy::ecs::entity::{EntityMapper, MapEntities};
+
+    /// A custom joint that doesn't constrain the bodies at all.
+    #[derive(Component)]
+    struct LooseJoint {
+        entity1: Entity,
+        entity2: Entity,
+    }
+
+    impl MapEntities for LooseJoint {
+        fn map_entities<M: EntityMapper>(&mut self, entity_mapper: &mut M) {
+            self.entity1 = entity_mapper.map_entity(self.entity1);
+            self.entity2 = entity_mapper.map_entity(self.entity2);
+        }
+    }
+
+    impl XpbdConstraint<2> for LooseJoint {
+        fn entities(&self) -> [Entity; 2] {
+            [self.entity1, self.entity2]
+        }
+
+        fn solve(&mut self, _bodies: [&mut RigidBodyQueryItem; 2], _dt: Scalar) {}
+
+        fn clear_lagrange_multipliers(&mut self) {}
+    }
+
+    impl PositionConstraint for LooseJoint {}
+
+    impl AngularConstraint for LooseJoint {}
+
+    impl Joint for LooseJoint {
+        fn new(entity1: Entity, entity2: Entity) -> Self {
+            Self { entity1, entity2 }
+        }
+        fn with_compliance(self, _compliance: Scalar) -> Self {
+            self
+        }
+        fn with_local_anchor_1(self, _anchor: Vector) -> Self {
+            self
+        }
+        fn with_local_anchor_2(self, _anchor: Vector) -> Self {
+            self
+        }
+        fn with_linear_velocity_damping(self, _damping: Scalar) -> Self {
+            self
+        }
+        fn with_angular_velocity_damping(self, _damping: Scalar) -> Self {
+            self
+        }
+        fn local_anchor_1(&self) -> Vector {
+            Vector::ZERO
+        }
+        fn local_anchor_2(&self) -> Vector {
+            Vector::ZERO
+        }
+        fn damping_linear(&self) -> Scalar {
+            0.0
+        }
+        fn damping_angular(&self) -> Scalar {
+            0.0
+        }
+    }
+
+    let mut app = create_app();
+
+    app.add_plugins(JointPlugin::<LooseJoint>::default())
+        .insert_resource(Gravity::ZERO);
+
+    app.finish();
+
+    let body1 = spawn_box(&mut app, Vector::ZERO);
+    let body2 = spawn_box(&mut app, Vector::X * 0.5);
+
+    app.world_mut().spawn(LooseJoint::new(body1, body2));
+
+    tick_60_fps(&mut app);
+
+    let collisions = app.world().resource::<Collisions>();
+    assert!(!collisions.contains(body1, body2));
+}
+
+#[test]
+fn collision_exclusions_filter_pairs() {
+    let mut app = create_app();
+
+    app.insert_resource(Gravity::ZERO);
+
+    app.finish();
+
+    // Two pairs of overlapping bodies.
+    let body1 = spawn_box(&mut app, Vector::ZERO);
+    let body2 = spawn_box(&mut app, Vector::X * 0.5);
+    let body3 = spawn_box(&mut app, Vector::Y * 10.0);
+    let body4 = spawn_box(&mut app, Vector::Y * 10.0 + Vector::X * 0.5);
+
+    let mut exclusions = app.world_mut().resource_mut::<CollisionExclusions>();
+    exclusions.insert(body1, body2);
+    exclusions.insert_for(body4, body3, Duration::from_secs_f32(0.1));
+
+    tick_60_fps(&mut app);
+
+    let collisions = app.world().resource::<Collisions>();
+    assert!(!collisions.contains(body1, body2));
+    assert!(!collisions.contains(body3, body4));
+
+    tick_frames(&mut app, 10);
+
+    // The second exclusion has expired.
+    let collisions = app.world().resource::<Collisions>();
+    assert!(!collisions.contains(body1, body2));
+    assert!(collisions.contains(body3, body4));
+
+    let exclusions = app.world().resource::<CollisionExclusions>();
+    assert!(exclusions.contains(body2, body1));
+    assert!(!exclusions.contains(body3, body4));
+
+    // Exclusions are removed when the entities are despawned.
+    app.world_mut().despawn(body1);
+    tick_60_fps(&mut app);
+    assert!(app.world().resource::<CollisionExclusions>().is_empty());
+}
+
+#[test]
+fn collision_observers_are_triggered() {
+    #[derive(Resource, Default)]
+    struct TriggeredEvents {
+        started: Vec<(Entity, OnCollisionStart)>,
+        ended: Vec<(Entity, OnCollisionEnd)>,
+    }
+
+    let mut app = create_app();
+
+    app.init_resource::<TriggeredEvents>()
+        .observe(
+            |trigger: Trigger<OnCollisionStart>, mut events: ResMut<TriggeredEvents>| {
+                events.started.push((trigger.entity(), *trigger.event()));
+            },
+        )
+        .observe(
+            |trigger: Trigger<OnCollisionEnd>, mut events: ResMut<TriggeredEvents>| {
+                events.ended.push((trigger.entity(), *trigger.event()));
+            },
+        );
+
+    app.finish();
+
+    let floor = spawn_floor(&mut app, Vector::ZERO, 10.0);
+
+    // A body with a child collider, resting on the floor.
+    let body = app
+        .world_mut()
+        .spawn((
+            RigidBody::Dynamic,
+            TransformBundle::from_transform(Transform::from_xyz(0.0, 1.0, 0.0)),
+        ))
+        .id();
+    let collider = app
+        .world_mut()
+        .spawn((box_collider(1.0, 1.0), TransformBundle::default()))
+        .set_parent(body)
+        .id();
+
+    tick_frames(&mut app, 10);
+
+    // The event is triggered for both colliders and for the body of the child collider.
+    let mut started =
+        std::mem::take(&mut app.world_mut().resource_mut::<TriggeredEvents>().started);
+    started.sort_by_key(|(entity, _)| *entity);
+    assert_eq!(
+        started,
+        vec![
+            (
+                floor,
+                OnCollisionStart {
+                    other: collider,
+                    other_body: Some(body),
+                }
+            ),
+            (
+                body,
+                OnCollisionStart {
+                    other: floor,
+                    other_body: Some(floor),
+                }
+            ),
+            (
+                collider,
+                OnCollisionStart {
+                    other: floor,
+                    other_body: Some(floor),
+                }
+            ),
+        ]
+    );
+    assert!(app.world().resource::<TriggeredEvents>().ended.is_empty());
+
+    // Move the body away from the floor.
+    app.world_mut().get_mut::<Position>(body).unwrap().0 = Vector::Y * 10.0;
+
+    tick_frames(&mut app, 2);
+
+    let ended = &app.world().resource::<TriggeredEvents>().ended;
+    assert_eq!(ended.len(), 3);
+    assert!(ended.contains(&(
+        floor,
+        OnCollisionEnd {
+            other: collider,
+            other_body: Some(body),
+        }
+    )));
+}
+
+#[test]
+fn collision_events_can_be_opt_in() {
+    let mut app = create_app();
+
+    app.insert_resource(Gravity::ZERO);
+    app.world_mut()
+        .resource_mut::<ContactReportingConfig>()
+        .events_enabled_by_default = false;
+
+    app.finish();
+
+    // Two pairs of overlapping bodies, where only one body has events enabled.
+    let body1 = spawn_box(&mut app, Vector::ZERO);
+    let body2 = spawn_box(&mut app, Vector::X * 0.5);
+    let body3 = spawn_box(&mut app, Vector::Y * 10.0);
+    let body4 = spawn_box(&mut app, Vector::Y * 10.0 + Vector::X * 0.5);
+
+    app.world_mut()
+        .entity_mut(body2)
+        .insert(CollisionEventsEnabled);
+
+    tick_60_fps(&mut app);
+
+    let events = app.world().resource::<Events<CollisionStarted>>();
+    let started = events
+        .get_reader()
+        .read(events)
+        .map(|CollisionStarted(entity1, entity2)| (*entity1, *entity2))
+        .collect::<Vec<_>>();
+    assert_eq!(started, vec![(body1, body2)]);
+
+    let events = app.world().resource::<Events<Collision>>();
+    assert!(events
+        .get_reader()
+        .read(events)
+        .all(|Collision(contacts)| contacts.entity1 == body1 && contacts.entity2 == body2));
+
+    // Colliding entities are still updated for all entities.
+    let colliding_entities = app.world().get::<CollidingEntities>(body3).unwrap();
+    assert!(colliding_entities.contains(&body4));
+}
+
+#[test]
+fn contact_force_events_respect_threshold() {
+    let mut app = create_app();
+
+    app.insert_resource(Gravity::ZERO);
+    app.finish();
+
+    let mut spawn_falling_box = |x: Scalar, threshold: Scalar| {
+        spawn_floor(&mut app, Vector::X * x, 5.0);
+        let body = spawn_box(&mut app, Vector::X * x + Vector::Y * 2.0);
+        app.world_mut().entity_mut(body).insert((
+            LinearVelocity(Vector::NEG_Y * 20.0),
+            ContactForceEventThreshold(threshold),
+        ));
+        body
+    };
+
+    // Only the first box has a threshold low enough for its impact to be reported.
+    let light_box = spawn_falling_box(0.0, 1.0);
+    let _heavy_box = spawn_falling_box(20.0, Scalar::MAX);
+
+    let mut reader = app
+        .world()
+        .resource::<Events<ContactForceEvent>>()
+        .get_reader();
+    let mut received = vec![];
+
+    for _ in 0..30 {
+        tick_60_fps(&mut app);
+        let events = app.world().resource::<Events<ContactForceEvent>>();
+        received.extend(reader.read(events).copied());
+    }
+
+    assert!(!received.is_empty());
+
+    let impact = received[0];
+    assert!(impact.entity1 == light_box || impact.entity2 == light_box);
+    assert!(impact.total_normal_force > 1.0);
+    assert!(impact.max_normal_force <= impact.total_normal_force);
+    assert!(impact.impact_speed > 10.0);
+    assert!(impact.normal.y.abs() > 0.99);
+
+    assert!(received
+        .iter()
+        .all(|event| event.entity1 == light_box || event.entity2 == light_box));
+}
+
+#[test]
+fn contact_force_events_report_the_dominant_contact_point() {
+    // Drops a tilted box so that it lands on its lowest corner,
+    // and returns the contact point of the first reported impact relative to the box.
+    let compute_impact_point = |angle: Scalar| {
+        let mut app = create_app();
+
+        app.insert_resource(Gravity::ZERO);
+        app.finish();
+
+        spawn_floor(&mut app, Vector::ZERO, 5.0);
+        let body = spawn_box(&mut app, Vector::Y * 2.0);
+        app.world_mut().entity_mut(body).insert((
+            #[cfg(feature = "2d")]
+            Rotation::radians(angle),
+            #[cfg(feature = "3d")]
+            Rotation(Quaternion::from_rotation_z(angle)),
+            LinearVelocity(Vector::NEG_Y * 20.0),
+            ContactForceEventThreshold(1.0),
+        ));
+
+        let mut reader = app
+            .world()
+            .resource::<Events<ContactForceEvent>>()
+            .get_reader();
+
+        for _ in 0..30 {
+            tick_60_fps(&mut app);
+            let events = app.world().resource::<Events<ContactForceEvent>>();
+            if let Some(event) = reader.read(events).next() {
+                return event.contact_point;
+            }
+        }
+
+        panic!("no contact force event was reported");
+    };
+
+    // The lowest corner of the box is on the opposite side of the rotation direction.
+    // The far contacts of the manifold are skipped by the solver, so the event must
+    // still report the corner that the box landed on.
+    for angle in [0.3, -0.3] {
+        let contact_point = compute_impact_point(angle);
+        assert!(contact_point.x * angle.signum() < -0.2);
+        assert!(contact_point.y.abs() < 0.6);
+    }
+}
+
+#[test]
+fn collision_impact_is_reported_on_collision_start() {
+    let mut app = create_app();
+
+    app.insert_resource(Gravity::ZERO);
+    app.finish();
+
+    let floor = spawn_floor(&mut app, Vector::ZERO, 5.0);
+    let body = spawn_box(&mut app, Vector::Y * 2.0);
+    app.world_mut()
+        .entity_mut(body)
+        .insert(LinearVelocity(Vector::NEG_Y * 10.0));
+
+    let mut reader = app
+        .world()
+        .resource::<Events<CollisionImpact>>()
+        .get_reader();
+    let mut impacts = vec![];
+
+    for _ in 0..30 {
+        tick_60_fps(&mut app);
+        let events = app.world().resource::<Events<CollisionImpact>>();
+        impacts.extend(reader.read(events).copied());
+    }
+
+    // The impact is only reported once, with the velocity from before the contact was solved.
+    assert_eq!(impacts.len(), 1);
+
+    let CollisionImpact(entity1, entity2, impact) = impacts[0];
+    assert!((entity1, entity2) == (floor, body) || (entity1, entity2) == (body, floor));
+    assert!((impact.approach_speed - 10.0).abs() < 0.1);
+    assert!((impact.relative_velocity.length() - 10.0).abs() < 0.1);
+    assert!(impact.normal.y.abs() > 0.99);
+}
+
 #[test]
 fn no_ambiguity_errors() {
     #[derive(ScheduleLabel, Clone, Debug, PartialEq, Eq, Hash)]
@@ -57,15 +57,22 @@ impl Plugin for PhysicsTypeRegistrationPlugin {
             .register_type::<CollidingEntities>()
             .register_type::<CoefficientCombine>()
             .register_type::<Sensor>()
+            .register_type::<SensorContacts>()
             .register_type::<ColliderTransform>()
             .register_type::<PreviousColliderTransform>()
             .register_type::<SpeculativeMargin>()
             .register_type::<SweptCcd>()
             .register_type::<CollisionMargin>()
+            .register_type::<SurfaceVelocity>()
+            .register_type::<SubShapeMaterials>()
             .register_type::<NarrowPhaseConfig>()
+            .register_type::<ContactCacheTolerance>()
             .register_type::<SolverConfig>()
             .register_type::<SyncConfig>()
+            .register_type::<Collider>()
             .register_type::<ColliderConstructor>()
+            .register_type::<ColliderConstructorConfig>()
+            .register_type::<ColliderConstructionPending>()
             .register_type::<ColliderConstructorHierarchy>()
             .register_type::<ColliderConstructorHierarchyConfig>()
             .register_type::<RayCaster>()